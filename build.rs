@@ -3,7 +3,12 @@ fn main() {
     if let Some(path) = std::env::var("BOX2D_LIB_DIR").ok() {
         println!("cargo:rustc-link-search=native={}", path);
     } else {
-        let box2d_install_prefix = cmake::Config::new("box2d")
+        let mut config = cmake::Config::new("box2d");
+        // assertion failures panic, and the panic has to unwind through Box2D's frames
+        if !std::env::var("TARGET").unwrap().contains("msvc") {
+            config.cflag("-fexceptions");
+        }
+        let box2d_install_prefix = config
             .define("CMAKE_MSVC_RUNTIME_LIBRARY", "MultiThreaded")
             .define("BOX2D_SAMPLES", "OFF")
             .define("BOX2D_VALIDATE", "OFF")
@@ -0,0 +1,229 @@
+use std::{
+    alloc::Layout,
+    cell::Cell,
+    ffi::{c_int, c_uint, c_void},
+    mem::size_of,
+    ptr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use crate::{error::AllocatorInUse, world::WORLD_SLOTS};
+
+/// A source of memory for Box2D. Implementations must be usable from any thread,
+/// since Box2D may allocate from its worker tasks.
+pub trait Allocator: Send + Sync {
+    fn alloc(&self, layout: Layout) -> *mut u8;
+
+    /// # Safety
+    /// `ptr` was returned by [Allocator::alloc] on this allocator with the same `layout`.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout);
+}
+
+/// Sends Box2D allocations to the Rust global allocator.
+pub struct RustAllocator;
+
+impl Allocator for RustAllocator {
+    fn alloc(&self, layout: Layout) -> *mut u8 {
+        unsafe { std::alloc::alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        std::alloc::dealloc(ptr, layout)
+    }
+}
+
+/// A snapshot of allocation counters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Bytes currently allocated.
+    pub bytes: usize,
+    /// Highest value `bytes` has reached.
+    pub peak_bytes: usize,
+    /// Allocations that have not been freed yet.
+    pub live_allocations: usize,
+    /// Allocations made since the counters were created.
+    pub total_allocations: usize,
+}
+
+pub(crate) struct AllocCounter {
+    bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+    live_allocations: AtomicUsize,
+    total_allocations: AtomicUsize,
+}
+
+impl AllocCounter {
+    pub const fn new() -> Self {
+        Self {
+            bytes: AtomicUsize::new(0),
+            peak_bytes: AtomicUsize::new(0),
+            live_allocations: AtomicUsize::new(0),
+            total_allocations: AtomicUsize::new(0),
+        }
+    }
+
+    fn record_alloc(&self, size: usize) {
+        let bytes = self.bytes.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak_bytes.fetch_max(bytes, Ordering::Relaxed);
+        self.live_allocations.fetch_add(1, Ordering::Relaxed);
+        self.total_allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_free(&self, size: usize) {
+        self.bytes.fetch_sub(size, Ordering::Relaxed);
+        self.live_allocations.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> AllocStats {
+        AllocStats {
+            bytes: self.bytes.load(Ordering::Relaxed),
+            peak_bytes: self.peak_bytes.load(Ordering::Relaxed),
+            live_allocations: self.live_allocations.load(Ordering::Relaxed),
+            total_allocations: self.total_allocations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+static ALLOCATOR: RwLock<Option<&'static dyn Allocator>> = RwLock::new(None);
+static GLOBAL_COUNTER: AllocCounter = AllocCounter::new();
+
+thread_local! {
+    /// The world whose call is currently running on this thread, if any.
+    static CURRENT_COUNTER: Cell<*const AllocCounter> = const { Cell::new(ptr::null()) };
+}
+
+/// Stored in front of every allocation, since Box2D's free callback only gets the pointer.
+#[repr(C)]
+struct Header {
+    /// Size and alignment of the whole block, header included.
+    size: u32,
+    align: u32,
+    allocator: &'static dyn Allocator,
+    /// From `Arc::into_raw`, or null if no world was attributed.
+    counter: *const AllocCounter,
+}
+
+/// Box2D asks for 32 byte alignment, which also leaves room for the header.
+const MIN_ALIGN: usize = 32;
+const _: () = assert!(size_of::<Header>() <= MIN_ALIGN);
+
+/// Route Box2D allocations through `allocator`. This is global to the process.
+///
+/// The first call has to happen before Box2D allocates anything with the C
+/// allocator, which in practice means before the first world is created.
+/// After that, the allocator can be swapped at any time.
+pub fn set_allocator(allocator: &'static dyn Allocator) -> Result<(), AllocatorInUse> {
+    // No world may be created while checking that nothing has been allocated yet.
+    // Taken before ALLOCATOR, in the same order as World::new.
+    let _lock = WORLD_SLOTS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut current = ALLOCATOR.write().unwrap_or_else(|e| e.into_inner());
+    if current.is_none() {
+        if byte_count() != 0 {
+            return Err(AllocatorInUse);
+        }
+        unsafe { b2SetAllocator(alloc_trampoline, free_trampoline) };
+    }
+    *current = Some(allocator);
+    Ok(())
+}
+
+/// Bytes Box2D currently has allocated, as tracked by Box2D itself.
+pub fn byte_count() -> usize {
+    unsafe { b2GetByteCount() as usize }
+}
+
+/// Counters for every allocation made through [set_allocator].
+pub fn global_stats() -> AllocStats {
+    GLOBAL_COUNTER.snapshot()
+}
+
+/// Attributes allocations on this thread to a world until dropped.
+pub(crate) struct AllocScope {
+    previous: *const AllocCounter,
+}
+
+impl AllocScope {
+    pub fn enter(counter: &Arc<AllocCounter>) -> Self {
+        let previous = CURRENT_COUNTER.with(|c| c.replace(Arc::as_ptr(counter)));
+        Self { previous }
+    }
+}
+
+impl Drop for AllocScope {
+    fn drop(&mut self) {
+        CURRENT_COUNTER.with(|c| c.set(self.previous));
+    }
+}
+
+extern "C-unwind" fn alloc_trampoline(size: c_uint, alignment: c_int) -> *mut c_void {
+    let allocator = ALLOCATOR
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .expect("allocator hook installed without an allocator");
+
+    let align = (alignment as usize).max(MIN_ALIGN);
+    let size = size as usize;
+    let Ok(layout) = Layout::from_size_align(size + align, align) else {
+        return ptr::null_mut();
+    };
+    if layout.size() > u32::MAX as usize {
+        return ptr::null_mut();
+    }
+    let base = allocator.alloc(layout);
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+
+    let counter = CURRENT_COUNTER.with(|c| c.get());
+    if !counter.is_null() {
+        unsafe {
+            Arc::increment_strong_count(counter);
+            (*counter).record_alloc(size);
+        }
+    }
+    GLOBAL_COUNTER.record_alloc(size);
+
+    unsafe {
+        let user = base.add(align);
+        let header = user.sub(size_of::<Header>()) as *mut Header;
+        header.write(Header {
+            size: layout.size() as u32,
+            align: layout.align() as u32,
+            allocator,
+            counter,
+        });
+        user as *mut c_void
+    }
+}
+
+extern "C-unwind" fn free_trampoline(mem: *mut c_void) {
+    if mem.is_null() {
+        return;
+    }
+    unsafe {
+        let user = mem as *mut u8;
+        let header = (user.sub(size_of::<Header>()) as *const Header).read();
+        let layout = Layout::from_size_align_unchecked(header.size as usize, header.align as usize);
+        let align = layout.align();
+        let size = layout.size() - align;
+
+        GLOBAL_COUNTER.record_free(size);
+        if !header.counter.is_null() {
+            let counter = Arc::from_raw(header.counter);
+            counter.record_free(size);
+        }
+
+        header.allocator.dealloc(user.sub(align), layout);
+    }
+}
+
+type AllocFcn = extern "C-unwind" fn(size: c_uint, alignment: c_int) -> *mut c_void;
+type FreeFcn = extern "C-unwind" fn(mem: *mut c_void);
+
+extern "C-unwind" {
+    fn b2SetAllocator(alloc_fcn: AllocFcn, free_fcn: FreeFcn);
+    fn b2GetByteCount() -> c_int;
+}
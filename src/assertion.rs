@@ -0,0 +1,79 @@
+use std::{
+    ffi::{c_char, c_int, CStr},
+    sync::{Once, RwLock},
+};
+
+/// A failed assertion inside Box2D.
+#[derive(Debug, Clone)]
+pub struct AssertFailure {
+    pub condition: String,
+    pub file: String,
+    pub line: u32,
+}
+
+/// Called when Box2D hits a failed assertion. If the handler returns instead of
+/// panicking, Box2D carries on past the assertion, which is rarely safe.
+pub type AssertHandler = fn(&AssertFailure);
+
+static HANDLER: RwLock<AssertHandler> = RwLock::new(panic_handler);
+static INSTALL: Once = Once::new();
+
+/// The default handler. Panics with the condition and location, so the failure
+/// comes with a Rust backtrace. A world that panicked mid-call should not be used again.
+pub fn panic_handler(failure: &AssertFailure) {
+    panic!(
+        "Box2D assertion failed: {} at {}:{}",
+        failure.condition, failure.file, failure.line
+    );
+}
+
+/// Replace the handler for Box2D assertions. This is global to the process.
+pub fn set_assert_handler(handler: AssertHandler) {
+    *HANDLER.write().unwrap_or_else(|e| e.into_inner()) = handler;
+    install();
+}
+
+/// Route Box2D assertions to the current handler. Called when a world is created,
+/// and before each geometry function that calls into Box2D without a world.
+pub(crate) fn install() {
+    INSTALL.call_once(|| unsafe { b2SetAssertFcn(assert_trampoline) });
+}
+
+extern "C-unwind" fn assert_trampoline(
+    condition: *const c_char,
+    file: *const c_char,
+    line: c_int,
+) -> c_int {
+    let to_string = |s: *const c_char| {
+        if s.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned()
+        }
+    };
+    let failure = AssertFailure {
+        condition: to_string(condition),
+        file: to_string(file),
+        line: line as u32,
+    };
+
+    #[cfg(feature = "log")]
+    log::error!(
+        "Box2D assertion failed: {} at {}:{}",
+        failure.condition,
+        failure.file,
+        failure.line
+    );
+
+    let handler = *HANDLER.read().unwrap_or_else(|e| e.into_inner());
+    handler(&failure);
+
+    // zero tells Box2D not to trigger a breakpoint
+    0
+}
+
+type AssertFcn = extern "C-unwind" fn(*const c_char, *const c_char, c_int) -> c_int;
+
+extern "C-unwind" {
+    fn b2SetAssertFcn(assert_fcn: AssertFcn);
+}
@@ -1,10 +1,18 @@
+use std::cell::Ref;
+
 use crate::{
-    math::{Rot, Vec2},
-    shapes::{Circle, Polygon},
-    PhantomNoSend, Shape, ShapeDef, World,
+    chain::{Chain, ChainDef, ChainId, RawChainDef},
+    contact::{collect_contacts, ContactData},
+    error::{
+        check_finite, check_finite_vec, check_non_negative, check_positive, CreateError, DefError,
+    },
+    joint::JointId,
+    math::{Rot, Transform, Vec2},
+    shapes::{Capsule, Circle, Geometry, MassData, Polygon, Segment, ShapeId},
+    Shape, ShapeDef, StaleHandle, World,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[repr(C)]
 pub struct BodyDef {
     /// The body type: static, kinematic, or dynamic.
@@ -43,8 +51,8 @@ pub struct BodyDef {
     /// Sleep velocity threshold, default is 0.05 meter per second
     pub sleep_threshold: f32,
 
-    /// Use this to store application specific body data.
-    pub user_data: *const std::ffi::c_void,
+    /// Not used by the wrapper. Attach typed data with [Body::set_user_data] instead.
+    user_data: *const std::ffi::c_void,
 
     /// Set this flag to false if this body should never fall asleep.
     pub enable_sleep: bool,
@@ -76,14 +84,29 @@ pub struct BodyDef {
     _cookie: u32,
 }
 
+/// Identifies a body independently of any borrow of its [World]. Use
+/// [World::body] to turn it back into a [Body].
 #[repr(C)]
-#[derive(Clone)]
-pub struct Body {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BodyId {
     index: u32,
     world: u16,
     revision: u16,
-    _thread_unsafe: PhantomNoSend,
 }
+
+/// A handle to a body, borrowed from the [World] that owns it.
+pub struct Body<'w, B = (), S = ()> {
+    id: BodyId,
+    world: &'w World<B, S>,
+}
+
+impl<B, S> Clone for Body<'_, B, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B, S> Copy for Body<'_, B, S> {}
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(C)]
 pub enum BodyKind {
@@ -98,28 +121,717 @@ impl Default for BodyDef {
     }
 }
 
-impl Body {
-    pub fn create_shape_polygon(&self, shape_def: &ShapeDef, polygon: &Polygon) -> Shape {
-        unsafe { b2CreatePolygonShape(self.clone(), shape_def, polygon) }
+impl BodyDef {
+    /// Check the definition for values Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        check_finite_vec("position", self.position)?;
+        check_finite("rotation", self.rotation.cos)?;
+        check_finite("rotation", self.rotation.sin)?;
+        if !self.rotation.is_normalized() {
+            return Err(DefError::RotationNotNormalized);
+        }
+        check_finite_vec("linear_velocity", self.linear_velocity)?;
+        check_finite("angular_velocity", self.angular_velocity)?;
+        check_non_negative("linear_damping", self.linear_damping)?;
+        check_non_negative("angular_damping", self.angular_damping)?;
+        check_finite("gravity_scale", self.gravity_scale)?;
+        check_non_negative("sleep_threshold", self.sleep_threshold)?;
+        Ok(())
+    }
+}
+
+impl<'w, B, S> Body<'w, B, S> {
+    pub(crate) fn new(world: &'w World<B, S>, id: BodyId) -> Self {
+        Self { id, world }
+    }
+
+    pub fn id(&self) -> BodyId {
+        self.id
+    }
+
+    pub fn world(&self) -> &'w World<B, S> {
+        self.world
+    }
+
+    /// Is this body still alive in the world that created this handle?
+    pub fn is_valid(&self) -> bool {
+        // body ids store a zero-based world index
+        self.id.world as u32 + 1 == self.world.id().index as u32
+            && unsafe { b2Body_IsValid(self.id) }
+    }
+
+    /// Get the raw id, or fail if the body is gone. Every call into Box2D goes through this.
+    pub(crate) fn checked_id(&self) -> Result<BodyId, StaleHandle> {
+        if self.is_valid() {
+            Ok(self.id)
+        } else {
+            Err(StaleHandle)
+        }
+    }
+
+    /// Borrow the user data attached to this body. While the borrow is held,
+    /// creating or destroying bodies, or changing their user data, panics.
+    pub fn user_data(&self) -> Result<Option<Ref<'w, B>>, StaleHandle> {
+        let id = self.checked_id()?;
+        let registry = self.world.body_registry().borrow();
+        Ok(Ref::filter_map(registry, |registry| registry.get(&id)).ok())
+    }
+
+    /// Attach user data to this body, dropping any previous value.
+    ///
+    /// # Panics
+    ///
+    /// If the user data of any body in the world is borrowed, see [Body::user_data].
+    pub fn set_user_data(&self, data: B) -> Result<(), StaleHandle> {
+        let id = self.checked_id()?;
+        self.world.body_registry().borrow_mut().set(id, Some(data));
+        Ok(())
+    }
+
+    /// Drop the user data attached to this body.
+    ///
+    /// # Panics
+    ///
+    /// If the user data of any body in the world is borrowed, see [Body::user_data].
+    pub fn clear_user_data(&self) -> Result<(), StaleHandle> {
+        let id = self.checked_id()?;
+        self.world.body_registry().borrow_mut().set(id, None);
+        Ok(())
+    }
+
+    /// Destroy this body, along with all of its shapes and joints. Other handles
+    /// to the body or its shapes become stale.
+    ///
+    /// # Panics
+    ///
+    /// If the user data of any body or shape in the world is borrowed.
+    pub fn destroy(self) -> Result<(), StaleHandle> {
+        let id = self.checked_id()?;
+        let shape_ids = self.shape_ids();
+        let chain_ids: Vec<ChainId> = shape_ids
+            .iter()
+            .filter_map(|shape_id| Shape::new(self.world, *shape_id).chain().ok().flatten())
+            .map(|chain| chain.id())
+            .collect();
+        // borrowed up front, so a borrowed user data panics before Box2D is touched
+        let mut body_data = self.world.body_registry().borrow_mut();
+        let mut shape_data = self.world.shape_registry().borrow_mut();
+        {
+            let _scope = self.world.alloc_scope();
+            unsafe {
+                b2DestroyBody(id);
+            }
+        }
+
+        body_data.remove(&id);
+        let mut chains = self.world.chain_segments().borrow_mut();
+        for chain_id in &chain_ids {
+            chains.remove(chain_id);
+        }
+        for shape_id in &shape_ids {
+            shape_data.remove(shape_id);
+        }
+        Ok(())
+    }
+
+    pub fn shape_count(&self) -> Result<usize, StaleHandle> {
+        Ok(unsafe { b2Body_GetShapeCount(self.checked_id()?) } as usize)
     }
 
-    pub fn create_shape_circle(&self, shape_def: &ShapeDef, circle: &Circle) -> Shape {
-        unsafe { b2CreateCircleShape(self.clone(), shape_def, circle) }
+    /// Every shape attached to this body.
+    pub fn shapes(&self) -> Result<Vec<Shape<'w, B, S>>, StaleHandle> {
+        self.checked_id()?;
+        let shapes = self.shape_ids();
+        Ok(shapes
+            .into_iter()
+            .map(|id| Shape::new(self.world, id))
+            .collect())
     }
 
-    pub fn set_angular_velocity(&self, ang_vel: f32) {
+    pub fn joint_count(&self) -> Result<usize, StaleHandle> {
+        Ok(unsafe { b2Body_GetJointCount(self.checked_id()?) } as usize)
+    }
+
+    /// Every joint attached to this body.
+    pub fn joints(&self) -> Result<Vec<JointId>, StaleHandle> {
+        let id = self.checked_id()?;
         unsafe {
-            b2Body_SetAngularVelocity(self.clone(), ang_vel);
+            let count = b2Body_GetJointCount(id);
+            let mut ids = Vec::with_capacity(count as usize);
+            let written = b2Body_GetJoints(id, ids.as_mut_ptr(), count);
+            ids.set_len(written as usize);
+            Ok(ids)
         }
     }
+
+    /// Every touching contact on this body, as of the last step.
+    pub fn contact_data(&self) -> Result<Vec<ContactData>, StaleHandle> {
+        let id = self.checked_id()?;
+        let capacity = unsafe { b2Body_GetContactCapacity(id) };
+        Ok(collect_contacts(capacity, |buffer, capacity| unsafe {
+            b2Body_GetContactData(id, buffer, capacity)
+        }))
+    }
+
+    /// Ids of every shape on this body. Assumes the body is valid.
+    pub(crate) fn shape_ids(&self) -> Vec<ShapeId> {
+        unsafe {
+            let count = b2Body_GetShapeCount(self.id);
+            let mut ids = Vec::with_capacity(count as usize);
+            let written = b2Body_GetShapes(self.id, ids.as_mut_ptr(), count);
+            ids.set_len(written as usize);
+            ids
+        }
+    }
+
+    fn register_shape(&self, id: ShapeId) -> Shape<'w, B, S> {
+        self.world.shape_registry().borrow_mut().insert(id);
+        Shape::new(self.world, id)
+    }
+
+    pub fn create_shape_polygon(
+        &self,
+        shape_def: &ShapeDef,
+        polygon: &Polygon,
+    ) -> Result<Shape<'w, B, S>, CreateError> {
+        let body_id = self.checked_id()?;
+        shape_def.validate()?;
+        polygon.validate()?;
+        let _scope = self.world.alloc_scope();
+        let id = unsafe { b2CreatePolygonShape(body_id, shape_def, polygon) };
+        Ok(self.register_shape(id))
+    }
+
+    pub fn create_shape_circle(
+        &self,
+        shape_def: &ShapeDef,
+        circle: &Circle,
+    ) -> Result<Shape<'w, B, S>, CreateError> {
+        let body_id = self.checked_id()?;
+        shape_def.validate()?;
+        circle.validate()?;
+        let _scope = self.world.alloc_scope();
+        let id = unsafe { b2CreateCircleShape(body_id, shape_def, circle) };
+        Ok(self.register_shape(id))
+    }
+
+    pub fn create_shape_capsule(
+        &self,
+        shape_def: &ShapeDef,
+        capsule: &Capsule,
+    ) -> Result<Shape<'w, B, S>, CreateError> {
+        let body_id = self.checked_id()?;
+        shape_def.validate()?;
+        capsule.validate()?;
+        let _scope = self.world.alloc_scope();
+        let id = unsafe { b2CreateCapsuleShape(body_id, shape_def, capsule) };
+        Ok(self.register_shape(id))
+    }
+
+    /// Segments have no mass, so they are best used on static bodies.
+    pub fn create_shape_segment(
+        &self,
+        shape_def: &ShapeDef,
+        segment: &Segment,
+    ) -> Result<Shape<'w, B, S>, CreateError> {
+        let body_id = self.checked_id()?;
+        shape_def.validate()?;
+        segment.validate()?;
+        let _scope = self.world.alloc_scope();
+        let id = unsafe { b2CreateSegmentShape(body_id, shape_def, segment) };
+        Ok(self.register_shape(id))
+    }
+
+    /// Create a chain of segments on this body. Chains are meant for static terrain.
+    pub fn create_chain(&self, def: &ChainDef) -> Result<Chain<'w, B, S>, CreateError> {
+        let body_id = self.checked_id()?;
+        def.validate()?;
+        let raw = def.raw();
+        let id = {
+            let _scope = self.world.alloc_scope();
+            unsafe { b2CreateChain(body_id, &raw) }
+        };
+
+        // Box2D lists a body's newest shape first, so this puts the segments back
+        // in the order they were made along the chain
+        let segments: Vec<ShapeId> = self
+            .shape_ids()
+            .into_iter()
+            .rev()
+            .filter(|shape_id| {
+                let chain = Shape::new(self.world, *shape_id).chain();
+                matches!(chain, Ok(Some(chain)) if chain.id() == id)
+            })
+            .collect();
+        let mut shape_data = self.world.shape_registry().borrow_mut();
+        for shape_id in &segments {
+            shape_data.insert(*shape_id);
+        }
+        self.world
+            .chain_segments()
+            .borrow_mut()
+            .insert(id, segments);
+        Ok(Chain::new(self.world, id))
+    }
+
+    pub fn create_shape(
+        &self,
+        shape_def: &ShapeDef,
+        geometry: &Geometry,
+    ) -> Result<Shape<'w, B, S>, CreateError> {
+        match geometry {
+            Geometry::Circle(circle) => self.create_shape_circle(shape_def, circle),
+            Geometry::Capsule(capsule) => self.create_shape_capsule(shape_def, capsule),
+            Geometry::Segment(segment) => self.create_shape_segment(shape_def, segment),
+            Geometry::Polygon(polygon) => self.create_shape_polygon(shape_def, polygon),
+        }
+    }
+
+    /// The world position of the body's origin.
+    pub fn position(&self) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Body_GetPosition(self.checked_id()?) })
+    }
+
+    pub fn rotation(&self) -> Result<Rot, StaleHandle> {
+        Ok(unsafe { b2Body_GetRotation(self.checked_id()?) })
+    }
+
+    pub fn transform(&self) -> Result<Transform, StaleHandle> {
+        Ok(unsafe { b2Body_GetTransform(self.checked_id()?) })
+    }
+
+    /// Teleport the body. This breaks contacts and wakes the body, so prefer
+    /// velocities for anything that moves every frame.
+    pub fn set_transform(&self, position: Vec2, rotation: Rot) -> Result<(), CreateError> {
+        let _scope = self.world.alloc_scope();
+        let id = self.checked_id()?;
+        check_finite_vec("position", position)?;
+        if !rotation.is_normalized() {
+            return Err(DefError::RotationNotNormalized.into());
+        }
+        unsafe {
+            b2Body_SetTransform(id, position, rotation);
+        }
+        Ok(())
+    }
+
+    /// Set the velocities so the body arrives at `target` at the end of the next
+    /// step, which must use the same `time_step`. Unlike [Body::set_transform]
+    /// this moves the body through the world, so contacts are kept and other
+    /// bodies get pushed along. Meant for kinematic bodies.
+    ///
+    /// Fails with [DefError::NotPositive] if `time_step` is zero or negative,
+    /// since no velocity can reach the target in no time.
+    pub fn set_target_transform(
+        &self,
+        target: &Transform,
+        time_step: f32,
+    ) -> Result<(), CreateError> {
+        let _scope = self.world.alloc_scope();
+        let id = self.checked_id()?;
+        check_positive("time_step", time_step)?;
+        check_finite_vec("target", target.pos)?;
+        if !target.rot.is_normalized() {
+            return Err(DefError::RotationNotNormalized.into());
+        }
+
+        unsafe {
+            let center = b2Body_GetWorldCenterOfMass(id);
+            let target_center = target * b2Body_GetLocalCenterOfMass(id);
+            let rotation = b2Body_GetRotation(id);
+
+            let linear_velocity = (target_center - center) / time_step;
+            let angular_velocity = rotation.angle_to(target.rot) / time_step;
+
+            b2Body_SetLinearVelocity(id, linear_velocity);
+            b2Body_SetAngularVelocity(id, angular_velocity);
+        }
+        Ok(())
+    }
+
+    /// The linear velocity of the body's center of mass.
+    pub fn linear_velocity(&self) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Body_GetLinearVelocity(self.checked_id()?) })
+    }
+
+    pub fn set_linear_velocity(&self, velocity: Vec2) -> Result<(), CreateError> {
+        let _scope = self.world.alloc_scope();
+        let id = self.checked_id()?;
+        check_finite_vec("linear_velocity", velocity)?;
+        unsafe {
+            b2Body_SetLinearVelocity(id, velocity);
+        }
+        Ok(())
+    }
+
+    /// Angular velocity in radians per second.
+    pub fn angular_velocity(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Body_GetAngularVelocity(self.checked_id()?) })
+    }
+
+    pub fn set_angular_velocity(&self, ang_vel: f32) -> Result<(), CreateError> {
+        let _scope = self.world.alloc_scope();
+        let id = self.checked_id()?;
+        check_finite("angular_velocity", ang_vel)?;
+        unsafe {
+            b2Body_SetAngularVelocity(id, ang_vel);
+        }
+        Ok(())
+    }
+
+    /// Apply a force at a world point. If the force is not applied at the center of mass,
+    /// it will generate a torque and affect the angular velocity. Setting `wake` wakes
+    /// the body up, otherwise the force is ignored while the body sleeps.
+    pub fn apply_force(&self, force: Vec2, point: Vec2, wake: bool) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_ApplyForce(self.checked_id()?, force, point, wake);
+        }
+        Ok(())
+    }
+
+    /// Apply a force to the center of mass.
+    pub fn apply_force_to_center(&self, force: Vec2, wake: bool) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_ApplyForceToCenter(self.checked_id()?, force, wake);
+        }
+        Ok(())
+    }
+
+    /// Apply a torque. This affects the angular velocity without affecting the linear velocity.
+    pub fn apply_torque(&self, torque: f32, wake: bool) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_ApplyTorque(self.checked_id()?, torque, wake);
+        }
+        Ok(())
+    }
+
+    /// Apply an impulse at a world point. This immediately modifies the velocity, and
+    /// also the angular velocity if the point is not the center of mass.
+    pub fn apply_linear_impulse(
+        &self,
+        impulse: Vec2,
+        point: Vec2,
+        wake: bool,
+    ) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_ApplyLinearImpulse(self.checked_id()?, impulse, point, wake);
+        }
+        Ok(())
+    }
+
+    /// Apply an impulse to the center of mass. This immediately modifies the velocity.
+    pub fn apply_linear_impulse_to_center(
+        &self,
+        impulse: Vec2,
+        wake: bool,
+    ) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_ApplyLinearImpulseToCenter(self.checked_id()?, impulse, wake);
+        }
+        Ok(())
+    }
+
+    /// Apply an angular impulse. This immediately modifies the angular velocity.
+    pub fn apply_angular_impulse(&self, impulse: f32, wake: bool) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_ApplyAngularImpulse(self.checked_id()?, impulse, wake);
+        }
+        Ok(())
+    }
+
+    pub fn kind(&self) -> Result<BodyKind, StaleHandle> {
+        Ok(unsafe { b2Body_GetType(self.checked_id()?) })
+    }
+
+    /// Change the body type. This is an expensive operation: it resets the mass and
+    /// velocity, and updates contacts and the broad-phase.
+    pub fn set_kind(&self, kind: BodyKind) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_SetType(self.checked_id()?, kind);
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Body_IsEnabled(self.checked_id()?) })
+    }
+
+    /// A disabled body is removed from the simulation: it does not move or
+    /// collide, and its joints are inactive. This is expensive.
+    pub fn set_enabled(&self, flag: bool) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        let id = self.checked_id()?;
+        unsafe {
+            if flag {
+                b2Body_Enable(id);
+            } else {
+                b2Body_Disable(id);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_awake(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Body_IsAwake(self.checked_id()?) })
+    }
+
+    /// Wake a body, or put it to sleep along with everything touching it.
+    pub fn set_awake(&self, flag: bool) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_SetAwake(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    pub fn is_sleep_enabled(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Body_IsSleepEnabled(self.checked_id()?) })
+    }
+
+    /// Allow or prevent this body from falling asleep. Disabling sleep wakes the body.
+    pub fn enable_sleep(&self, flag: bool) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Body_EnableSleep(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    /// Sleep velocity threshold, usually in meters per second.
+    pub fn sleep_threshold(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Body_GetSleepThreshold(self.checked_id()?) })
+    }
+
+    pub fn set_sleep_threshold(&self, threshold: f32) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_non_negative("sleep_threshold", threshold)?;
+        unsafe {
+            b2Body_SetSleepThreshold(id, threshold);
+        }
+        Ok(())
+    }
+
+    pub fn is_bullet(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Body_IsBullet(self.checked_id()?) })
+    }
+
+    /// Treat this body as a high speed object that performs continuous collision
+    /// against other dynamic and kinematic bodies.
+    pub fn set_bullet(&self, flag: bool) -> Result<(), StaleHandle> {
+        unsafe {
+            b2Body_SetBullet(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    pub fn is_fixed_rotation(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Body_IsFixedRotation(self.checked_id()?) })
+    }
+
+    /// Prevent the body from rotating. This also resets the angular velocity.
+    pub fn set_fixed_rotation(&self, flag: bool) -> Result<(), StaleHandle> {
+        unsafe {
+            b2Body_SetFixedRotation(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    pub fn linear_damping(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Body_GetLinearDamping(self.checked_id()?) })
+    }
+
+    pub fn set_linear_damping(&self, damping: f32) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_non_negative("linear_damping", damping)?;
+        unsafe {
+            b2Body_SetLinearDamping(id, damping);
+        }
+        Ok(())
+    }
+
+    pub fn angular_damping(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Body_GetAngularDamping(self.checked_id()?) })
+    }
+
+    pub fn set_angular_damping(&self, damping: f32) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_non_negative("angular_damping", damping)?;
+        unsafe {
+            b2Body_SetAngularDamping(id, damping);
+        }
+        Ok(())
+    }
+
+    pub fn gravity_scale(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Body_GetGravityScale(self.checked_id()?) })
+    }
+
+    pub fn set_gravity_scale(&self, scale: f32) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_finite("gravity_scale", scale)?;
+        unsafe {
+            b2Body_SetGravityScale(id, scale);
+        }
+        Ok(())
+    }
+
+    /// The mass of the body, usually in kilograms.
+    pub fn mass(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Body_GetMass(self.checked_id()?) })
+    }
+
+    /// The rotational inertia of the body about the center of mass, usually in kg*m^2.
+    pub fn rotational_inertia(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Body_GetRotationalInertia(self.checked_id()?) })
+    }
+
+    /// The center of mass in body coordinates.
+    pub fn local_center_of_mass(&self) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Body_GetLocalCenterOfMass(self.checked_id()?) })
+    }
+
+    pub fn mass_data(&self) -> Result<MassData, StaleHandle> {
+        Ok(unsafe { b2Body_GetMassData(self.checked_id()?) })
+    }
+
+    /// Override the mass properties. Normally this is computed from the shapes
+    /// automatically, but this is the only way to give a body mass when
+    /// [BodyDef::automatic_mass] is off.
+    pub fn set_mass_data(&self, mass_data: MassData) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        mass_data.validate()?;
+        unsafe {
+            b2Body_SetMassData(id, mass_data);
+        }
+        Ok(())
+    }
+
+    /// Is the mass recomputed whenever shapes are added, removed or reshaped?
+    pub fn has_automatic_mass(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Body_GetAutomaticMass(self.checked_id()?) })
+    }
+
+    /// See [BodyDef::automatic_mass]. Turning it back on does not recompute the
+    /// mass until the shapes change or [Body::apply_mass_from_shapes] is called.
+    pub fn set_automatic_mass(&self, flag: bool) -> Result<(), StaleHandle> {
+        unsafe {
+            b2Body_SetAutomaticMass(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    /// Recompute the mass properties from the attached shapes. Use this after
+    /// changing shape densities, or when automatic mass is off.
+    pub fn apply_mass_from_shapes(&self) -> Result<(), StaleHandle> {
+        unsafe {
+            b2Body_ApplyMassFromShapes(self.checked_id()?);
+        }
+        Ok(())
+    }
+
+    /// Convert a point in body coordinates to world coordinates.
+    pub fn world_point(&self, local_point: Vec2) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Body_GetWorldPoint(self.checked_id()?, local_point) })
+    }
+
+    /// Convert a point in world coordinates to body coordinates.
+    pub fn local_point(&self, world_point: Vec2) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Body_GetLocalPoint(self.checked_id()?, world_point) })
+    }
+
+    /// Rotate a vector from body coordinates to world coordinates.
+    pub fn world_vector(&self, local_vector: Vec2) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Body_GetWorldVector(self.checked_id()?, local_vector) })
+    }
+
+    /// Rotate a vector from world coordinates to body coordinates.
+    pub fn local_vector(&self, world_vector: Vec2) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Body_GetLocalVector(self.checked_id()?, world_vector) })
+    }
+
+    pub fn world_center_of_mass(&self) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Body_GetWorldCenterOfMass(self.checked_id()?) })
+    }
 }
 
-extern "C" {
-    fn b2CreatePolygonShape(body: Body, shape_def: &ShapeDef, polygon: &Polygon) -> Shape;
+extern "C-unwind" {
+    fn b2Body_IsValid(body: BodyId) -> bool;
+    fn b2DestroyBody(body: BodyId);
+
+    fn b2Body_GetShapeCount(body: BodyId) -> i32;
+    fn b2Body_GetShapes(body: BodyId, shapes: *mut ShapeId, capacity: i32) -> i32;
+    fn b2Body_GetContactCapacity(body: BodyId) -> i32;
+    fn b2Body_GetContactData(body: BodyId, contacts: *mut ContactData, capacity: i32) -> i32;
+    fn b2Body_GetJointCount(body: BodyId) -> i32;
+    fn b2Body_GetJoints(body: BodyId, joints: *mut JointId, capacity: i32) -> i32;
+
+    fn b2CreatePolygonShape(body: BodyId, shape_def: &ShapeDef, polygon: &Polygon) -> ShapeId;
+
+    fn b2CreateCircleShape(body: BodyId, shape_def: &ShapeDef, circle: &Circle) -> ShapeId;
+    fn b2CreateCapsuleShape(body: BodyId, shape_def: &ShapeDef, capsule: &Capsule) -> ShapeId;
+    fn b2CreateSegmentShape(body: BodyId, shape_def: &ShapeDef, segment: &Segment) -> ShapeId;
+    fn b2CreateChain(body: BodyId, def: &RawChainDef) -> ChainId;
+
+    fn b2Body_GetPosition(body: BodyId) -> Vec2;
+    fn b2Body_GetRotation(body: BodyId) -> Rot;
+    fn b2Body_GetTransform(body: BodyId) -> Transform;
+    fn b2Body_SetTransform(body: BodyId, position: Vec2, rotation: Rot);
+
+    fn b2Body_GetLinearVelocity(body: BodyId) -> Vec2;
+    fn b2Body_SetLinearVelocity(body: BodyId, velocity: Vec2);
+    fn b2Body_GetAngularVelocity(body: BodyId) -> f32;
+    fn b2Body_SetAngularVelocity(body: BodyId, ang_vel: f32);
+
+    fn b2Body_ApplyForce(body: BodyId, force: Vec2, point: Vec2, wake: bool);
+    fn b2Body_ApplyForceToCenter(body: BodyId, force: Vec2, wake: bool);
+    fn b2Body_ApplyTorque(body: BodyId, torque: f32, wake: bool);
+    fn b2Body_ApplyLinearImpulse(body: BodyId, impulse: Vec2, point: Vec2, wake: bool);
+    fn b2Body_ApplyLinearImpulseToCenter(body: BodyId, impulse: Vec2, wake: bool);
+    fn b2Body_ApplyAngularImpulse(body: BodyId, impulse: f32, wake: bool);
+
+    fn b2Body_GetType(body: BodyId) -> BodyKind;
+    fn b2Body_SetType(body: BodyId, kind: BodyKind);
+    fn b2Body_IsEnabled(body: BodyId) -> bool;
+    fn b2Body_Enable(body: BodyId);
+    fn b2Body_Disable(body: BodyId);
+    fn b2Body_IsAwake(body: BodyId) -> bool;
+    fn b2Body_SetAwake(body: BodyId, awake: bool);
+    fn b2Body_IsSleepEnabled(body: BodyId) -> bool;
+    fn b2Body_EnableSleep(body: BodyId, enable_sleep: bool);
+    fn b2Body_GetSleepThreshold(body: BodyId) -> f32;
+    fn b2Body_SetSleepThreshold(body: BodyId, sleep_velocity: f32);
+    fn b2Body_IsBullet(body: BodyId) -> bool;
+    fn b2Body_SetBullet(body: BodyId, flag: bool);
+    fn b2Body_IsFixedRotation(body: BodyId) -> bool;
+    fn b2Body_SetFixedRotation(body: BodyId, flag: bool);
+    fn b2Body_GetLinearDamping(body: BodyId) -> f32;
+    fn b2Body_SetLinearDamping(body: BodyId, linear_damping: f32);
+    fn b2Body_GetAngularDamping(body: BodyId) -> f32;
+    fn b2Body_SetAngularDamping(body: BodyId, angular_damping: f32);
+    fn b2Body_GetGravityScale(body: BodyId) -> f32;
+    fn b2Body_SetGravityScale(body: BodyId, gravity_scale: f32);
 
-    fn b2CreateCircleShape(body: Body, shape_def: &ShapeDef, circle: &Circle) -> Shape;
+    fn b2Body_GetMass(body: BodyId) -> f32;
+    fn b2Body_GetRotationalInertia(body: BodyId) -> f32;
+    fn b2Body_GetLocalCenterOfMass(body: BodyId) -> Vec2;
+    fn b2Body_GetMassData(body: BodyId) -> MassData;
+    fn b2Body_SetMassData(body: BodyId, mass_data: MassData);
+    fn b2Body_ApplyMassFromShapes(body: BodyId);
+    fn b2Body_GetAutomaticMass(body: BodyId) -> bool;
+    fn b2Body_SetAutomaticMass(body: BodyId, automatic_mass: bool);
 
-    fn b2Body_SetAngularVelocity(body: Body, ang_vel: f32);
+    fn b2Body_GetWorldPoint(body: BodyId, local_point: Vec2) -> Vec2;
+    fn b2Body_GetLocalPoint(body: BodyId, world_point: Vec2) -> Vec2;
+    fn b2Body_GetWorldVector(body: BodyId, local_vector: Vec2) -> Vec2;
+    fn b2Body_GetLocalVector(body: BodyId, world_vector: Vec2) -> Vec2;
+    fn b2Body_GetWorldCenterOfMass(body: BodyId) -> Vec2;
 
     fn b2DefaultBodyDef() -> BodyDef;
 }
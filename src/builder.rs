@@ -0,0 +1,290 @@
+use crate::{
+    body::BodyKind,
+    common::Filter,
+    error::DefError,
+    math::{Rot, Vec2},
+    shapes::{Capsule, Circle, Geometry, Polygon, Segment},
+    Body, BodyDef, Shape, ShapeDef, World,
+};
+
+/// Describes a body and all of its shapes, so they can be created with a single
+/// call to [World::spawn].
+///
+/// Shapes added with [BodyBuilder::with_circle] and friends use the builder's
+/// shape settings ([BodyBuilder::density], [BodyBuilder::friction], ...), no
+/// matter where in the chain those are set. Use [BodyBuilder::with_shape] to
+/// give a single shape its own [ShapeDef].
+///
+/// Cloning a builder is an easy way to spawn many copies of the same body.
+#[derive(Clone)]
+pub struct BodyBuilder<B = (), S = ()> {
+    def: BodyDef,
+    user_data: Option<B>,
+    shape_def: ShapeDef,
+    shapes: Vec<PendingShape<S>>,
+}
+
+#[derive(Clone)]
+struct PendingShape<S> {
+    def: Option<ShapeDef>,
+    geometry: Geometry,
+    user_data: Option<S>,
+}
+
+/// A body created by [World::spawn], along with its shapes in the order they were added.
+pub struct SpawnedBody<'w, B = (), S = ()> {
+    pub body: Body<'w, B, S>,
+    pub shapes: Vec<Shape<'w, B, S>>,
+}
+
+impl<B, S> BodyBuilder<B, S> {
+    pub fn new(kind: BodyKind) -> Self {
+        let mut def = BodyDef::default();
+        def.kind = kind;
+        Self {
+            def,
+            user_data: None,
+            shape_def: ShapeDef::default(),
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn dynamic() -> Self {
+        Self::new(BodyKind::Dynamic)
+    }
+
+    pub fn kinematic() -> Self {
+        Self::new(BodyKind::Kinematic)
+    }
+
+    pub fn static_body() -> Self {
+        Self::new(BodyKind::Static)
+    }
+
+    /// Start from an existing body definition.
+    pub fn from_def(def: BodyDef) -> Self {
+        Self {
+            def,
+            user_data: None,
+            shape_def: ShapeDef::default(),
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn at(mut self, position: Vec2) -> Self {
+        self.def.position = position;
+        self
+    }
+
+    pub fn rotation(mut self, rotation: Rot) -> Self {
+        self.def.rotation = rotation;
+        self
+    }
+
+    /// Set the rotation from an angle in radians.
+    pub fn angle(mut self, angle: f32) -> Self {
+        self.def.rotation = Rot::from_angle(angle);
+        self
+    }
+
+    pub fn linear_velocity(mut self, velocity: Vec2) -> Self {
+        self.def.linear_velocity = velocity;
+        self
+    }
+
+    pub fn angular_velocity(mut self, velocity: f32) -> Self {
+        self.def.angular_velocity = velocity;
+        self
+    }
+
+    pub fn linear_damping(mut self, damping: f32) -> Self {
+        self.def.linear_damping = damping;
+        self
+    }
+
+    pub fn angular_damping(mut self, damping: f32) -> Self {
+        self.def.angular_damping = damping;
+        self
+    }
+
+    pub fn gravity_scale(mut self, scale: f32) -> Self {
+        self.def.gravity_scale = scale;
+        self
+    }
+
+    pub fn sleep_threshold(mut self, threshold: f32) -> Self {
+        self.def.sleep_threshold = threshold;
+        self
+    }
+
+    pub fn enable_sleep(mut self, flag: bool) -> Self {
+        self.def.enable_sleep = flag;
+        self
+    }
+
+    pub fn awake(mut self, flag: bool) -> Self {
+        self.def.is_awake = flag;
+        self
+    }
+
+    pub fn fixed_rotation(mut self, flag: bool) -> Self {
+        self.def.fixed_rotation = flag;
+        self
+    }
+
+    pub fn bullet(mut self, flag: bool) -> Self {
+        self.def.is_bullet = flag;
+        self
+    }
+
+    pub fn enabled(mut self, flag: bool) -> Self {
+        self.def.is_enabled = flag;
+        self
+    }
+
+    pub fn automatic_mass(mut self, flag: bool) -> Self {
+        self.def.automatic_mass = flag;
+        self
+    }
+
+    pub fn allow_fast_rotation(mut self, flag: bool) -> Self {
+        self.def.allow_fast_rotation = flag;
+        self
+    }
+
+    pub fn user_data(mut self, data: B) -> Self {
+        self.user_data = Some(data);
+        self
+    }
+
+    pub fn friction(mut self, friction: f32) -> Self {
+        self.shape_def.friction = friction;
+        self
+    }
+
+    pub fn restitution(mut self, restitution: f32) -> Self {
+        self.shape_def.restitution = restitution;
+        self
+    }
+
+    pub fn density(mut self, density: f32) -> Self {
+        self.shape_def.density = density;
+        self
+    }
+
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.shape_def.filter = filter;
+        self
+    }
+
+    pub fn custom_color(mut self, color: u32) -> Self {
+        self.shape_def.custom_color = color;
+        self
+    }
+
+    pub fn sensor(mut self, flag: bool) -> Self {
+        self.shape_def.is_sensor = flag;
+        self
+    }
+
+    pub fn enable_sensor_events(mut self, flag: bool) -> Self {
+        self.shape_def.enable_sensor_events = flag;
+        self
+    }
+
+    pub fn enable_contact_events(mut self, flag: bool) -> Self {
+        self.shape_def.enable_contact_events = flag;
+        self
+    }
+
+    pub fn enable_hit_events(mut self, flag: bool) -> Self {
+        self.shape_def.enable_hit_events = flag;
+        self
+    }
+
+    pub fn enable_pre_solve_events(mut self, flag: bool) -> Self {
+        self.shape_def.enable_pre_solve_events = flag;
+        self
+    }
+
+    pub fn force_contact_creation(mut self, flag: bool) -> Self {
+        self.shape_def.force_contact_creation = flag;
+        self
+    }
+
+    pub fn with_circle(self, circle: Circle) -> Self {
+        self.with_geometry(circle.into())
+    }
+
+    pub fn with_capsule(self, capsule: Capsule) -> Self {
+        self.with_geometry(capsule.into())
+    }
+
+    pub fn with_segment(self, segment: Segment) -> Self {
+        self.with_geometry(segment.into())
+    }
+
+    pub fn with_polygon(self, polygon: Polygon) -> Self {
+        self.with_geometry(polygon.into())
+    }
+
+    /// Add a shape that ignores the builder's shape settings and uses `def` instead.
+    pub fn with_shape(
+        mut self,
+        def: ShapeDef,
+        geometry: impl Into<Geometry>,
+        user_data: Option<S>,
+    ) -> Self {
+        self.shapes.push(PendingShape {
+            def: Some(def),
+            geometry: geometry.into(),
+            user_data,
+        });
+        self
+    }
+
+    fn with_geometry(mut self, geometry: Geometry) -> Self {
+        self.shapes.push(PendingShape {
+            def: None,
+            geometry,
+            user_data: None,
+        });
+        self
+    }
+
+    /// Check everything up front, so a bad shape never leaves a half built body behind.
+    pub fn validate(&self) -> Result<(), DefError> {
+        self.def.validate()?;
+        self.shape_def.validate()?;
+        for shape in &self.shapes {
+            if let Some(def) = &shape.def {
+                def.validate()?;
+            }
+            shape.geometry.validate()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn build(self, world: &World<B, S>) -> Result<SpawnedBody<'_, B, S>, DefError> {
+        self.validate()?;
+
+        let body = world.create_body(&self.def)?;
+        if let Some(data) = self.user_data {
+            body.set_user_data(data).expect("body was just created");
+        }
+
+        let mut shapes = Vec::with_capacity(self.shapes.len());
+        for pending in self.shapes {
+            let def = pending.def.as_ref().unwrap_or(&self.shape_def);
+            let shape = body
+                .create_shape(def, &pending.geometry)
+                .expect("shapes were validated");
+            if let Some(data) = pending.user_data {
+                shape.set_user_data(data).expect("shape was just created");
+            }
+            shapes.push(shape);
+        }
+
+        Ok(SpawnedBody { body, shapes })
+    }
+}
@@ -0,0 +1,243 @@
+use std::ffi::c_void;
+
+use crate::{
+    common::Filter,
+    error::{check_finite_vec, check_non_negative, CreateError, DefError},
+    math::Vec2,
+    shapes::LINEAR_SLOP,
+    Body, Shape, ShapeId, StaleHandle, World,
+};
+
+/// Describes a chain of one-sided segments. Chains collide on their right side,
+/// so a looped chain should be given counter-clockwise to keep things outside it.
+///
+/// Unlike the other definitions this is not passed to Box2D as is, since Box2D
+/// only borrows the points.
+#[derive(Debug, Clone)]
+pub struct ChainDef {
+    /// The chain vertices, at least 4. Internal edges of the chain never catch
+    /// moving shapes, unlike separate segments or boxes.
+    ///
+    /// A looped chain gets a segment for every point. An open chain only uses its
+    /// first and last points to smooth the ends, so `n` points give `n - 3` segments.
+    pub points: Vec<Vec2>,
+
+    /// The friction coefficient, usually in the range [0,1].
+    pub friction: f32,
+
+    /// The restitution (elasticity) usually in the range [0,1].
+    pub restitution: f32,
+
+    /// Contact filtering data.
+    pub filter: Filter,
+
+    /// Indicates a closed chain formed by connecting the first and last vertices
+    pub is_loop: bool,
+}
+
+/// The layout Box2D expects. Borrows the points of the [ChainDef] it was made from.
+#[repr(C)]
+pub(crate) struct RawChainDef {
+    user_data: *mut c_void,
+    points: *const Vec2,
+    count: i32,
+    friction: f32,
+    restitution: f32,
+    filter: Filter,
+    is_loop: bool,
+    _cookie: u32,
+}
+
+/// Identifies a chain independently of any borrow of its [World]. Use
+/// [World::chain] to turn it back into a [Chain].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChainId {
+    index: u32,
+    world: u16,
+    revision: u16,
+}
+
+/// A handle to a chain, borrowed from the [World] that owns it. Each segment of
+/// the chain is a separate shape on the chain's body, see [Chain::segments].
+pub struct Chain<'w, B = (), S = ()> {
+    id: ChainId,
+    world: &'w World<B, S>,
+}
+
+impl<B, S> Clone for Chain<'_, B, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B, S> Copy for Chain<'_, B, S> {}
+
+impl Default for ChainDef {
+    fn default() -> Self {
+        let raw = unsafe { b2DefaultChainDef() };
+        Self {
+            points: Vec::new(),
+            friction: raw.friction,
+            restitution: raw.restitution,
+            filter: raw.filter,
+            is_loop: raw.is_loop,
+        }
+    }
+}
+
+impl ChainDef {
+    pub fn new(points: Vec<Vec2>, is_loop: bool) -> Self {
+        Self {
+            points,
+            is_loop,
+            ..Default::default()
+        }
+    }
+
+    /// Check the definition for values Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        if self.points.len() < 4 {
+            return Err(DefError::ChainTooShort);
+        }
+        for point in &self.points {
+            check_finite_vec("points", *point)?;
+        }
+        let closing = self
+            .is_loop
+            .then(|| [self.points[self.points.len() - 1], self.points[0]]);
+        let too_close = self
+            .points
+            .windows(2)
+            .map(|pair| [pair[0], pair[1]])
+            .chain(closing)
+            .any(|[a, b]| (b - a).length() <= LINEAR_SLOP);
+        if too_close {
+            return Err(DefError::DegenerateSegment);
+        }
+        check_non_negative("friction", self.friction)?;
+        check_non_negative("restitution", self.restitution)
+    }
+
+    pub(crate) fn raw(&self) -> RawChainDef {
+        let mut raw = unsafe { b2DefaultChainDef() };
+        raw.points = self.points.as_ptr();
+        raw.count = self.points.len() as i32;
+        raw.friction = self.friction;
+        raw.restitution = self.restitution;
+        raw.filter = self.filter.clone();
+        raw.is_loop = self.is_loop;
+        raw
+    }
+}
+
+impl ChainId {
+    /// Box2D uses a zero index for shapes that are not part of a chain.
+    pub(crate) fn is_null(&self) -> bool {
+        self.index == 0
+    }
+}
+
+impl<'w, B, S> Chain<'w, B, S> {
+    pub(crate) fn new(world: &'w World<B, S>, id: ChainId) -> Self {
+        Self { id, world }
+    }
+
+    pub fn id(&self) -> ChainId {
+        self.id
+    }
+
+    pub fn world(&self) -> &'w World<B, S> {
+        self.world
+    }
+
+    /// Is this chain still alive in the world that created this handle?
+    pub fn is_valid(&self) -> bool {
+        // chain ids store a zero-based world index
+        self.id.world as u32 + 1 == self.world.id().index as u32
+            && unsafe { b2Chain_IsValid(self.id) }
+    }
+
+    /// Get the raw id, or fail if the chain is gone. Every call into Box2D goes through this.
+    pub(crate) fn checked_id(&self) -> Result<ChainId, StaleHandle> {
+        if self.is_valid() {
+            Ok(self.id)
+        } else {
+            Err(StaleHandle)
+        }
+    }
+
+    /// The body this chain is attached to.
+    pub fn body(&self) -> Result<Body<'w, B, S>, StaleHandle> {
+        let segments = self.segment_ids()?;
+        // Box2D always creates at least one segment per chain, so no segments means
+        // the id belongs to a chain this world never created
+        let first = segments.first().ok_or(StaleHandle)?;
+        Shape::new(self.world, *first).body()
+    }
+
+    /// The segment shapes Box2D generated for this chain, in order. See
+    /// [ChainDef::points] for how many there are. They are owned by the chain,
+    /// so destroy the chain rather than its segments.
+    pub fn segments(&self) -> Result<Vec<Shape<'w, B, S>>, StaleHandle> {
+        let ids = self.segment_ids()?;
+        Ok(ids
+            .into_iter()
+            .map(|id| Shape::new(self.world, id))
+            .collect())
+    }
+
+    fn segment_ids(&self) -> Result<Vec<ShapeId>, StaleHandle> {
+        let id = self.checked_id()?;
+        let chains = self.world.chain_segments().borrow();
+        Ok(chains.get(&id).cloned().unwrap_or_default())
+    }
+
+    /// Set the friction of every segment in the chain.
+    pub fn set_friction(&self, friction: f32) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_non_negative("friction", friction)?;
+        unsafe { b2Chain_SetFriction(id, friction) };
+        Ok(())
+    }
+
+    /// Set the restitution of every segment in the chain.
+    pub fn set_restitution(&self, restitution: f32) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_non_negative("restitution", restitution)?;
+        unsafe { b2Chain_SetRestitution(id, restitution) };
+        Ok(())
+    }
+
+    /// Destroy the chain and all of its segments. Other handles to the chain
+    /// or its segments become stale.
+    ///
+    /// # Panics
+    ///
+    /// If the user data of any shape in the world is borrowed, see [Shape::user_data].
+    pub fn destroy(self) -> Result<(), StaleHandle> {
+        let segments = self.segment_ids()?;
+        let mut shape_data = self.world.shape_registry().borrow_mut();
+        {
+            let _scope = self.world.alloc_scope();
+            unsafe {
+                b2DestroyChain(self.id);
+            }
+        }
+
+        self.world.chain_segments().borrow_mut().remove(&self.id);
+        for shape_id in &segments {
+            shape_data.remove(shape_id);
+        }
+        Ok(())
+    }
+}
+
+extern "C-unwind" {
+    fn b2DefaultChainDef() -> RawChainDef;
+
+    fn b2DestroyChain(chain: ChainId);
+    fn b2Chain_IsValid(chain: ChainId) -> bool;
+    fn b2Chain_SetFriction(chain: ChainId, friction: f32);
+    fn b2Chain_SetRestitution(chain: ChainId, restitution: f32);
+}
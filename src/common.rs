@@ -1,5 +1,5 @@
 #[repr(C)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Filter {
     pub category_bits: u32,
     pub mask_bits: u32,
@@ -0,0 +1,99 @@
+use crate::{math::Vec2, shapes::ShapeId};
+
+/// A manifold point is a contact point belonging to a contact
+/// manifold. It holds details related to the geometry and dynamics
+/// of the contact points.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ManifoldPoint {
+    /// Location of the contact point in world space. Subject to precision loss at large coordinates.
+    /// @note Should only be used for debugging.
+    pub point: Vec2,
+
+    /// Location of the contact point relative to bodyA's origin in world space
+    pub anchor_a: Vec2,
+
+    /// Location of the contact point relative to bodyB's origin in world space
+    pub anchor_b: Vec2,
+
+    /// The separation of the contact point, negative if penetrating
+    pub separation: f32,
+
+    /// The impulse along the manifold normal vector.
+    pub normal_impulse: f32,
+
+    /// The friction impulse
+    pub tangent_impulse: f32,
+
+    /// The maximum normal impulse applied during sub-stepping
+    pub max_normal_impulse: f32,
+
+    /// Relative normal velocity pre-solve. Used for hit events. If the normal impulse is
+    /// zero then there was no hit. Negative means shapes are approaching.
+    pub normal_velocity: f32,
+
+    /// Uniquely identifies a contact point between two shapes
+    pub id: u16,
+
+    /// Did this contact point exist the previous step?
+    pub persisted: bool,
+}
+
+/// A contact manifold describes the contact points between colliding shapes
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Manifold {
+    /// The manifold points, up to two are possible in 2D
+    points: [ManifoldPoint; 2],
+
+    /// The unit normal vector in world space, points from shape A to shape B
+    normal: Vec2,
+
+    /// The number of contacts points, will be 0, 1, or 2
+    point_count: i32,
+}
+
+impl Manifold {
+    /// The unit normal vector in world space, pointing from shape A to shape B.
+    pub fn normal(&self) -> Vec2 {
+        self.normal
+    }
+
+    /// The contact points that are in use.
+    pub fn points(&self) -> &[ManifoldPoint] {
+        let count = self.point_count.clamp(0, 2) as usize;
+        &self.points[..count]
+    }
+}
+
+/// The contact data for two shapes. By convention the manifold normal points
+/// from shape A to shape B. Use [crate::World::shape] to get handles for the shapes.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ContactData {
+    pub shape_a: ShapeId,
+    pub shape_b: ShapeId,
+    pub manifold: Manifold,
+}
+
+impl ContactData {
+    /// Given one of the two shapes, get the other one.
+    pub fn other_shape(&self, shape: ShapeId) -> ShapeId {
+        if shape == self.shape_a {
+            self.shape_b
+        } else {
+            self.shape_a
+        }
+    }
+}
+
+/// Fill a buffer using Box2D's capacity/getter pair.
+pub(crate) fn collect_contacts(
+    capacity: i32,
+    get: impl FnOnce(*mut ContactData, i32) -> i32,
+) -> Vec<ContactData> {
+    let mut contacts = Vec::with_capacity(capacity.max(0) as usize);
+    let written = get(contacts.as_mut_ptr(), capacity.max(0));
+    unsafe { contacts.set_len(written.clamp(0, capacity.max(0)) as usize) };
+    contacts
+}
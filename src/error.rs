@@ -0,0 +1,171 @@
+use std::fmt;
+
+use crate::math::Vec2;
+
+/// Returned when a handle refers to a body or shape that no longer exists,
+/// or that belongs to a different live world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleHandle;
+
+impl fmt::Display for StaleHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "handle refers to a destroyed object")
+    }
+}
+
+impl std::error::Error for StaleHandle {}
+
+/// Describes why a world, body or shape definition was rejected. Box2D would
+/// otherwise assert or silently accept the bad value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DefError {
+    /// A field was NaN or infinite.
+    NotFinite(&'static str),
+    /// A field that must be zero or greater was negative.
+    Negative(&'static str),
+    /// A field that must be strictly positive was zero or negative.
+    NotPositive(&'static str),
+    /// A rotation's cosine and sine do not form a unit vector.
+    RotationNotNormalized,
+    /// A polygon has too few or too many vertices.
+    DegeneratePolygon,
+    /// A segment's end points are too close together for Box2D to collide it.
+    DegenerateSegment,
+    /// A polygon capsule's centers are too close together to give it a direction.
+    DegenerateCapsule,
+    /// A chain has fewer than 4 points.
+    ChainTooShort,
+    /// A path has no points.
+    EmptyPath,
+    /// More workers were asked for than Box2D supports, see [crate::world::MAX_WORKERS].
+    TooManyWorkers,
+}
+
+impl fmt::Display for DefError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefError::NotFinite(field) => write!(f, "{} must be finite", field),
+            DefError::Negative(field) => write!(f, "{} must not be negative", field),
+            DefError::NotPositive(field) => write!(f, "{} must be greater than zero", field),
+            DefError::RotationNotNormalized => write!(f, "rotation is not normalized"),
+            DefError::DegeneratePolygon => write!(f, "polygon has too few or too many vertices"),
+            DefError::DegenerateSegment => write!(f, "segment end points are too close together"),
+            DefError::DegenerateCapsule => write!(f, "capsule centers are too close together"),
+            DefError::ChainTooShort => write!(f, "chain has fewer than 4 points"),
+            DefError::EmptyPath => write!(f, "path has no points"),
+            DefError::TooManyWorkers => write!(
+                f,
+                "worker_count must be at most {}",
+                crate::world::MAX_WORKERS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DefError {}
+
+/// Returned when creating or changing something on a body, which can fail either
+/// because the body is gone or because the definition is invalid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CreateError {
+    StaleHandle,
+    InvalidDef(DefError),
+    /// The shape is a segment owned by a chain, which only the chain may change or destroy.
+    ChainSegment,
+}
+
+impl From<StaleHandle> for CreateError {
+    fn from(_: StaleHandle) -> Self {
+        CreateError::StaleHandle
+    }
+}
+
+impl From<DefError> for CreateError {
+    fn from(err: DefError) -> Self {
+        CreateError::InvalidDef(err)
+    }
+}
+
+impl fmt::Display for CreateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CreateError::StaleHandle => StaleHandle.fmt(f),
+            CreateError::InvalidDef(err) => err.fmt(f),
+            CreateError::ChainSegment => write!(f, "shape is owned by a chain"),
+        }
+    }
+}
+
+impl std::error::Error for CreateError {}
+
+/// Describes why a convex hull could not be computed from a set of points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HullError {
+    /// A hull needs between 3 and 8 points. Holds the number of points given.
+    PointCount(usize),
+    /// A point was NaN or infinite.
+    NotFinite,
+    /// The points are collinear or too close together to enclose an area.
+    Degenerate,
+}
+
+impl fmt::Display for HullError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HullError::PointCount(count) => {
+                write!(f, "hull needs between 3 and 8 points, got {}", count)
+            }
+            HullError::NotFinite => write!(f, "hull points must be finite"),
+            HullError::Degenerate => write!(f, "hull points do not enclose an area"),
+        }
+    }
+}
+
+impl std::error::Error for HullError {}
+
+pub(crate) fn check_finite(field: &'static str, value: f32) -> Result<(), DefError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(DefError::NotFinite(field))
+    }
+}
+
+pub(crate) fn check_finite_vec(field: &'static str, value: Vec2) -> Result<(), DefError> {
+    check_finite(field, value.x)?;
+    check_finite(field, value.y)
+}
+
+pub(crate) fn check_non_negative(field: &'static str, value: f32) -> Result<(), DefError> {
+    check_finite(field, value)?;
+    if value >= 0.0 {
+        Ok(())
+    } else {
+        Err(DefError::Negative(field))
+    }
+}
+
+pub(crate) fn check_positive(field: &'static str, value: f32) -> Result<(), DefError> {
+    check_finite(field, value)?;
+    if value > 0.0 {
+        Ok(())
+    } else {
+        Err(DefError::NotPositive(field))
+    }
+}
+
+/// Returned by [crate::allocator::set_allocator] when Box2D already holds memory
+/// from the C allocator, which the new allocator could not free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocatorInUse;
+
+impl fmt::Display for AllocatorInUse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Box2D has already allocated memory with the default allocator"
+        )
+    }
+}
+
+impl std::error::Error for AllocatorInUse {}
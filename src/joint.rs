@@ -0,0 +1,9 @@
+/// Identifies a joint. Joints can not be created through this crate yet, but
+/// bodies report the joints attached to them.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JointId {
+    index: u32,
+    world: u16,
+    revision: u16,
+}
@@ -1,16 +1,30 @@
 pub mod math;
 use std::marker::PhantomData;
 
+pub mod allocator;
+pub mod assertion;
 pub mod body;
+pub mod builder;
+pub mod chain;
 pub mod common;
+pub mod contact;
 pub mod debug_draw;
+pub mod error;
+pub mod joint;
+pub mod path;
+mod registry;
 pub mod shapes;
+mod task;
 pub mod world;
 
 /// Used to mark our handles as !Send and !Sync for some attempt at thread safety.
 type PhantomNoSend = PhantomData<*mut ()>;
 
-pub use body::{Body, BodyDef};
+pub use body::{Body, BodyDef, BodyId};
+pub use builder::{BodyBuilder, SpawnedBody};
+pub use chain::{Chain, ChainDef, ChainId};
+pub use error::{AllocatorInUse, CreateError, DefError, HullError, StaleHandle};
+pub use joint::JointId;
 pub use math::Vec2;
-pub use shapes::{Shape, ShapeDef};
+pub use shapes::{Shape, ShapeDef, ShapeId};
 pub use world::{World, WorldDef};
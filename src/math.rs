@@ -3,6 +3,7 @@
 use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
 
 #[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct AABB {
     pub lower_bound: Vec2,
     pub upper_bound: Vec2,
@@ -21,6 +22,10 @@ impl Vec2 {
     pub fn new(x: f32, y: f32) -> Self {
         Self { x, y }
     }
+
+    pub fn length(&self) -> f32 {
+        self.x.hypot(self.y)
+    }
 }
 
 impl Add for Vec2 {
@@ -40,6 +45,46 @@ impl AddAssign for Vec2 {
     }
 }
 
+impl Sub for Vec2 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl Div<f32> for Vec2 {
+    type Output = Self;
+    fn div(self, rhs: f32) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Rot {
@@ -76,6 +121,19 @@ impl Rot {
     pub fn angle(&self) -> f32 {
         self.sin.atan2(self.cos)
     }
+
+    /// The angle to rotate by to get from `self` to `other`, in the range [-pi, pi].
+    pub fn angle_to(&self, other: Rot) -> f32 {
+        let sin = other.sin * self.cos - other.cos * self.sin;
+        let cos = other.cos * self.cos + other.sin * self.sin;
+        sin.atan2(cos)
+    }
+
+    /// Same tolerance as Box2D's b2IsNormalized.
+    pub fn is_normalized(&self) -> bool {
+        let qq = self.sin * self.sin + self.cos * self.cos;
+        1.0 - 0.0006 < qq && qq < 1.0 + 0.0006
+    }
 }
 
 #[cfg(feature = "nalgebra")]
@@ -0,0 +1,216 @@
+//! Drive kinematic bodies along paths, for moving platforms and elevators.
+
+use crate::{
+    error::{check_finite, check_finite_vec, check_positive, DefError},
+    math::{Transform, Vec2},
+    BodyId, CreateError, World,
+};
+
+/// What happens when a [PathFollower] reaches the end of its path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathMode {
+    /// Stop at the last point.
+    Once,
+    /// Go back to the first point along an extra segment that closes the path.
+    Loop,
+    /// Turn around and go back the way it came.
+    PingPong,
+}
+
+/// How the path gets from one point to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathInterpolation {
+    /// Straight lines between the points.
+    Linear,
+    /// A Catmull-Rom spline through all of the points.
+    Spline,
+}
+
+/// Points per segment used to approximate a spline.
+const SPLINE_SAMPLES: usize = 16;
+
+/// Moves a body along a path at a constant speed, using
+/// [crate::Body::set_target_transform] so the body pushes and carries whatever
+/// it touches. The body keeps its rotation.
+///
+/// Call [PathFollower::update] once per frame, right before [World::step] and with
+/// the same time step.
+#[derive(Debug, Clone)]
+pub struct PathFollower {
+    body: BodyId,
+    mode: PathMode,
+    speed: f32,
+    /// The path flattened into line segments.
+    points: Vec<Vec2>,
+    /// Distance along the path at each entry of `points`.
+    distances: Vec<f32>,
+    /// Progress around the whole cycle. For ping-pong paths the way back counts
+    /// too, so this runs up to twice the length.
+    travelled: f32,
+}
+
+impl PathFollower {
+    /// `speed` is in meters per second along the path. Needs at least one point.
+    pub fn new(
+        body: BodyId,
+        waypoints: &[Vec2],
+        interpolation: PathInterpolation,
+        mode: PathMode,
+        speed: f32,
+    ) -> Result<Self, DefError> {
+        if waypoints.is_empty() {
+            return Err(DefError::EmptyPath);
+        }
+        for point in waypoints {
+            check_finite_vec("waypoints", *point)?;
+        }
+        check_finite("speed", speed)?;
+
+        let closed = mode == PathMode::Loop;
+        let points = match interpolation {
+            PathInterpolation::Linear => {
+                let mut points = waypoints.to_vec();
+                if closed {
+                    points.push(waypoints[0]);
+                }
+                points
+            }
+            PathInterpolation::Spline => sample_spline(waypoints, closed),
+        };
+
+        let mut distances = Vec::with_capacity(points.len());
+        let mut total = 0.0;
+        distances.push(0.0);
+        for pair in points.windows(2) {
+            total += (pair[1] - pair[0]).length();
+            distances.push(total);
+        }
+
+        Ok(Self {
+            body,
+            mode,
+            speed,
+            points,
+            distances,
+            travelled: 0.0,
+        })
+    }
+
+    pub fn body(&self) -> BodyId {
+        self.body
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f32) -> Result<(), DefError> {
+        check_finite("speed", speed)?;
+        self.speed = speed;
+        Ok(())
+    }
+
+    pub fn length(&self) -> f32 {
+        *self.distances.last().unwrap()
+    }
+
+    /// How far along the path the body should be after the last update.
+    pub fn distance(&self) -> f32 {
+        let length = self.length();
+        if self.travelled > length {
+            2.0 * length - self.travelled
+        } else {
+            self.travelled
+        }
+    }
+
+    /// True once a [PathMode::Once] path has reached its last point.
+    pub fn is_finished(&self) -> bool {
+        self.mode == PathMode::Once && self.travelled >= self.length()
+    }
+
+    /// The point on the path at `distance` from the start.
+    pub fn point_at(&self, distance: f32) -> Vec2 {
+        let distance = distance.clamp(0.0, self.length());
+        let next = self.distances.partition_point(|&d| d < distance);
+        if next == 0 {
+            return self.points[0];
+        }
+        let (d0, d1) = (self.distances[next - 1], self.distances[next]);
+        let (p0, p1) = (self.points[next - 1], self.points[next]);
+        if d1 <= d0 {
+            return p1;
+        }
+        p0 + (p1 - p0) * ((distance - d0) / (d1 - d0))
+    }
+
+    /// Advance along the path and set the body's velocity to get there by the end
+    /// of the next step. `time_step` must be positive, see [crate::Body::set_target_transform].
+    pub fn update<B, S>(&mut self, world: &World<B, S>, time_step: f32) -> Result<(), CreateError> {
+        let body = world.body(self.body)?;
+        check_positive("time_step", time_step)?;
+        self.advance(self.speed * time_step);
+
+        let target = Transform {
+            pos: self.point_at(self.distance()),
+            rot: body.rotation()?,
+        };
+        body.set_target_transform(&target, time_step)
+    }
+
+    fn advance(&mut self, step: f32) {
+        let length = self.length();
+        if length <= 0.0 {
+            return;
+        }
+
+        self.travelled = match self.mode {
+            PathMode::Once => (self.travelled + step).min(length),
+            PathMode::Loop => (self.travelled + step).rem_euclid(length),
+            PathMode::PingPong => (self.travelled + step).rem_euclid(2.0 * length),
+        };
+    }
+}
+
+/// Flatten a Catmull-Rom spline through `points` into line segments.
+fn sample_spline(points: &[Vec2], closed: bool) -> Vec<Vec2> {
+    let count = points.len();
+    if count < 2 {
+        return points.to_vec();
+    }
+
+    let get = |i: isize| -> Vec2 {
+        if closed {
+            points[i.rem_euclid(count as isize) as usize]
+        } else {
+            points[i.clamp(0, count as isize - 1) as usize]
+        }
+    };
+
+    let segments = if closed { count } else { count - 1 };
+    let mut samples = Vec::with_capacity(segments * SPLINE_SAMPLES + 1);
+    for segment in 0..segments as isize {
+        let (p0, p1, p2, p3) = (
+            get(segment - 1),
+            get(segment),
+            get(segment + 1),
+            get(segment + 2),
+        );
+        for sample in 0..SPLINE_SAMPLES {
+            let t = sample as f32 / SPLINE_SAMPLES as f32;
+            samples.push(catmull_rom(p0, p1, p2, p3, t));
+        }
+    }
+    samples.push(if closed { points[0] } else { points[count - 1] });
+    samples
+}
+
+fn catmull_rom(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, t: f32) -> Vec2 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (-p0 + p1 * 3.0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
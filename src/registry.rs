@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+/// Tracks every live body or shape created through the wrapper, along with its
+/// user data. Box2D has no way to enumerate these itself.
+pub(crate) struct Registry<K, T> {
+    /// Ordered by id, so iteration is deterministic.
+    live: BTreeMap<K, Option<T>>,
+}
+
+impl<K: Ord + Copy, T> Registry<K, T> {
+    pub fn new() -> Self {
+        Self {
+            live: BTreeMap::new(),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&T> {
+        self.live.get(key)?.as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.live.len()
+    }
+
+    pub fn keys(&self) -> Vec<K> {
+        self.live.keys().copied().collect()
+    }
+
+    /// Every entry that has user data.
+    pub fn entries(&self) -> impl Iterator<Item = (K, &T)> {
+        self.live
+            .iter()
+            .filter_map(|(key, value)| Some((*key, value.as_ref()?)))
+    }
+
+    pub fn insert(&mut self, key: K) {
+        self.live.insert(key, None);
+    }
+
+    /// Replace the user data, dropping the old value.
+    pub fn set(&mut self, key: K, value: Option<T>) {
+        self.live.insert(key, value);
+    }
+
+    pub fn remove(&mut self, key: &K) {
+        self.live.remove(key);
+    }
+}
@@ -1,10 +1,22 @@
-use crate::{common::Filter, math::Vec2, PhantomNoSend};
+use std::cell::Ref;
 
-#[derive(Debug)]
+use crate::{
+    chain::{Chain, ChainId},
+    common::Filter,
+    contact::{collect_contacts, ContactData},
+    error::{
+        check_finite, check_finite_vec, check_non_negative, check_positive, CreateError, DefError,
+        HullError,
+    },
+    math::{Transform, Vec2, AABB},
+    Body, BodyId, StaleHandle, World,
+};
+
+#[derive(Debug, Clone)]
 #[repr(C)]
 pub struct ShapeDef {
-    /// Use this to store application specific shape data.
-    pub user_data: *const std::ffi::c_void,
+    /// Not used by the wrapper. Attach typed data with [Shape::set_user_data] instead.
+    user_data: *const std::ffi::c_void,
 
     /// The Coulomb (dry) friction coefficient, usually in the range [0,1].
     pub friction: f32,
@@ -46,19 +58,49 @@ pub struct ShapeDef {
     _cookie: u32,
 }
 
+/// Identifies a shape independently of any borrow of its [World]. Use
+/// [World::shape] to turn it back into a [Shape].
 #[repr(C)]
-#[derive(Copy, Clone)]
-pub struct Shape {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShapeId {
     index: u32,
     world: u16,
     revision: u16,
-    _thread_unsafe: PhantomNoSend,
 }
 
+/// The type of geometry a shape was created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub enum ShapeType {
+    Circle = 0,
+    Capsule = 1,
+    Segment = 2,
+    Polygon = 3,
+    /// A segment that is part of a [Chain].
+    SmoothSegment = 4,
+}
+
+/// A handle to a shape, borrowed from the [World] that owns it.
+pub struct Shape<'w, B = (), S = ()> {
+    id: ShapeId,
+    world: &'w World<B, S>,
+}
+
+impl<B, S> Clone for Shape<'_, B, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B, S> Copy for Shape<'_, B, S> {}
+
 const MAX_POLYGON_VERTICES: usize = 8;
 
+/// Box2D's collision tolerance, in meters.
+pub(crate) const LINEAR_SLOP: f32 = 0.005;
+
 ///	DO NOT fill this out manually. Use a constructor.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub struct Polygon {
     /// The polygon vertices
@@ -77,56 +119,788 @@ pub struct Polygon {
     vertex_count: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub struct Hull {
     points: [Vec2; MAX_POLYGON_VERTICES],
     point_count: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(C)]
 pub struct Circle {
     pub center: Vec2,
     pub radius: f32,
 }
 
+/// A solid capsule can be viewed as two semicircles connected
+/// by a rectangle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Capsule {
+    /// Local center of the first semicircle
+    pub center1: Vec2,
+
+    /// Local center of the second semicircle
+    pub center2: Vec2,
+
+    /// The radius of the semicircles
+    pub radius: f32,
+}
+
+/// A line segment with two-sided collision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Segment {
+    /// The first point
+    pub point1: Vec2,
+
+    /// The second point
+    pub point2: Vec2,
+}
+
+/// Low level ray cast input data
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct RayCastInput {
+    /// Start point of the ray cast
+    pub origin: Vec2,
+
+    /// Translation of the ray cast
+    pub translation: Vec2,
+
+    /// The maximum fraction of the translation to consider, typically 1
+    pub max_fraction: f32,
+}
+
+/// Low level ray cast or shape-cast output data
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct CastOutput {
+    /// The surface normal at the hit point
+    pub normal: Vec2,
+
+    /// The surface hit point
+    pub point: Vec2,
+
+    /// The fraction of the input translation at collision
+    pub fraction: f32,
+
+    /// The number of iterations used
+    pub iterations: i32,
+
+    /// Did the cast hit?
+    pub hit: bool,
+}
+
+/// This holds the mass data computed for a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct MassData {
+    /// The mass of the shape, usually in kilograms.
+    pub mass: f32,
+
+    /// The position of the shape's centroid relative to the shape's origin.
+    pub center: Vec2,
+
+    /// The rotational inertia of the shape about the local origin.
+    pub rotational_inertia: f32,
+}
+
+/// Any of the shape geometries a body can be given.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Geometry {
+    Circle(Circle),
+    Capsule(Capsule),
+    Segment(Segment),
+    Polygon(Polygon),
+}
+
+impl From<Circle> for Geometry {
+    fn from(circle: Circle) -> Self {
+        Geometry::Circle(circle)
+    }
+}
+
+impl From<Capsule> for Geometry {
+    fn from(capsule: Capsule) -> Self {
+        Geometry::Capsule(capsule)
+    }
+}
+
+impl From<Segment> for Geometry {
+    fn from(segment: Segment) -> Self {
+        Geometry::Segment(segment)
+    }
+}
+
+impl From<Polygon> for Geometry {
+    fn from(polygon: Polygon) -> Self {
+        Geometry::Polygon(polygon)
+    }
+}
+
 impl Default for ShapeDef {
     fn default() -> Self {
         unsafe { b2DefaultShapeDef() }
     }
 }
 
-impl Shape {}
+impl ShapeDef {
+    /// Check the definition for values Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        check_non_negative("friction", self.friction)?;
+        check_non_negative("restitution", self.restitution)?;
+        check_non_negative("density", self.density)?;
+        Ok(())
+    }
+}
+
+impl<'w, B, S> Shape<'w, B, S> {
+    pub(crate) fn new(world: &'w World<B, S>, id: ShapeId) -> Self {
+        Self { id, world }
+    }
+
+    pub fn id(&self) -> ShapeId {
+        self.id
+    }
+
+    pub fn world(&self) -> &'w World<B, S> {
+        self.world
+    }
+
+    /// Is this shape still alive in the world that created this handle?
+    pub fn is_valid(&self) -> bool {
+        // shape ids store a zero-based world index
+        self.id.world as u32 + 1 == self.world.id().index as u32
+            && unsafe { b2Shape_IsValid(self.id) }
+    }
+
+    /// Get the raw id, or fail if the shape is gone. Every call into Box2D goes through this.
+    pub(crate) fn checked_id(&self) -> Result<ShapeId, StaleHandle> {
+        if self.is_valid() {
+            Ok(self.id)
+        } else {
+            Err(StaleHandle)
+        }
+    }
+
+    /// The body this shape is attached to.
+    pub fn body(&self) -> Result<Body<'w, B, S>, StaleHandle> {
+        let body_id = unsafe { b2Shape_GetBody(self.checked_id()?) };
+        Ok(Body::new(self.world, body_id))
+    }
+
+    /// The chain that owns this shape, if it is a chain segment.
+    pub fn chain(&self) -> Result<Option<Chain<'w, B, S>>, StaleHandle> {
+        let chain_id = unsafe { b2Shape_GetParentChain(self.checked_id()?) };
+        Ok((!chain_id.is_null()).then(|| Chain::new(self.world, chain_id)))
+    }
+
+    pub fn shape_type(&self) -> Result<ShapeType, StaleHandle> {
+        Ok(unsafe { b2Shape_GetType(self.checked_id()?) })
+    }
+
+    pub fn friction(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Shape_GetFriction(self.checked_id()?) })
+    }
+
+    pub fn set_friction(&self, friction: f32) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_non_negative("friction", friction)?;
+        unsafe {
+            b2Shape_SetFriction(id, friction);
+        }
+        Ok(())
+    }
+
+    pub fn restitution(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Shape_GetRestitution(self.checked_id()?) })
+    }
+
+    pub fn set_restitution(&self, restitution: f32) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_non_negative("restitution", restitution)?;
+        unsafe {
+            b2Shape_SetRestitution(id, restitution);
+        }
+        Ok(())
+    }
+
+    pub fn density(&self) -> Result<f32, StaleHandle> {
+        Ok(unsafe { b2Shape_GetDensity(self.checked_id()?) })
+    }
+
+    /// Set the density. The body's mass only changes if `update_body_mass` is set,
+    /// otherwise call [Body::apply_mass_from_shapes] once all shapes are updated.
+    pub fn set_density(&self, density: f32, update_body_mass: bool) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        check_non_negative("density", density)?;
+        unsafe {
+            b2Shape_SetDensity(id, density);
+        }
+        if update_body_mass {
+            self.body()?.apply_mass_from_shapes()?;
+        }
+        Ok(())
+    }
+
+    pub fn filter(&self) -> Result<Filter, StaleHandle> {
+        Ok(unsafe { b2Shape_GetFilter(self.checked_id()?) })
+    }
+
+    /// Change the collision filter. Contacts that are no longer allowed are
+    /// removed on the next step.
+    pub fn set_filter(&self, filter: Filter) -> Result<(), StaleHandle> {
+        let _scope = self.world.alloc_scope();
+        unsafe {
+            b2Shape_SetFilter(self.checked_id()?, filter);
+        }
+        Ok(())
+    }
+
+    /// Sensors are fixed when the shape is created, see [ShapeDef::is_sensor].
+    pub fn is_sensor(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Shape_IsSensor(self.checked_id()?) })
+    }
+
+    pub fn are_sensor_events_enabled(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Shape_AreSensorEventsEnabled(self.checked_id()?) })
+    }
+
+    pub fn enable_sensor_events(&self, flag: bool) -> Result<(), StaleHandle> {
+        unsafe {
+            b2Shape_EnableSensorEvents(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    pub fn are_contact_events_enabled(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Shape_AreContactEventsEnabled(self.checked_id()?) })
+    }
+
+    pub fn enable_contact_events(&self, flag: bool) -> Result<(), StaleHandle> {
+        unsafe {
+            b2Shape_EnableContactEvents(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    pub fn are_hit_events_enabled(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Shape_AreHitEventsEnabled(self.checked_id()?) })
+    }
+
+    pub fn enable_hit_events(&self, flag: bool) -> Result<(), StaleHandle> {
+        unsafe {
+            b2Shape_EnableHitEvents(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    pub fn are_pre_solve_events_enabled(&self) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Shape_ArePreSolveEventsEnabled(self.checked_id()?) })
+    }
+
+    pub fn enable_pre_solve_events(&self, flag: bool) -> Result<(), StaleHandle> {
+        unsafe {
+            b2Shape_EnablePreSolveEvents(self.checked_id()?, flag);
+        }
+        Ok(())
+    }
+
+    /// A copy of the shape's geometry, or `None` for chain segments.
+    pub fn geometry(&self) -> Result<Option<Geometry>, StaleHandle> {
+        let id = self.checked_id()?;
+        let geometry = unsafe {
+            match b2Shape_GetType(id) {
+                ShapeType::Circle => Some(Geometry::Circle(b2Shape_GetCircle(id))),
+                ShapeType::Capsule => Some(Geometry::Capsule(b2Shape_GetCapsule(id))),
+                ShapeType::Segment => Some(Geometry::Segment(b2Shape_GetSegment(id))),
+                ShapeType::Polygon => Some(Geometry::Polygon(b2Shape_GetPolygon(id))),
+                ShapeType::SmoothSegment => None,
+            }
+        };
+        Ok(geometry)
+    }
+
+    /// `None` if this is not a circle.
+    pub fn circle(&self) -> Result<Option<Circle>, StaleHandle> {
+        Ok(match self.geometry()? {
+            Some(Geometry::Circle(circle)) => Some(circle),
+            _ => None,
+        })
+    }
+
+    /// `None` if this is not a capsule.
+    pub fn capsule(&self) -> Result<Option<Capsule>, StaleHandle> {
+        Ok(match self.geometry()? {
+            Some(Geometry::Capsule(capsule)) => Some(capsule),
+            _ => None,
+        })
+    }
+
+    /// `None` if this is not a segment. Chain segments are not included.
+    pub fn segment(&self) -> Result<Option<Segment>, StaleHandle> {
+        Ok(match self.geometry()? {
+            Some(Geometry::Segment(segment)) => Some(segment),
+            _ => None,
+        })
+    }
+
+    /// `None` if this is not a polygon.
+    pub fn polygon(&self) -> Result<Option<Polygon>, StaleHandle> {
+        Ok(match self.geometry()? {
+            Some(Geometry::Polygon(polygon)) => Some(polygon),
+            _ => None,
+        })
+    }
+
+    /// Replace the shape's geometry, which may also change its type. Contacts
+    /// on the shape are rebuilt, and the body's mass is recomputed if it uses
+    /// automatic mass.
+    ///
+    /// Chain segments can not be changed, see [Chain::segments].
+    pub fn set_geometry(&self, geometry: &Geometry) -> Result<(), CreateError> {
+        match geometry {
+            Geometry::Circle(circle) => self.set_circle(circle),
+            Geometry::Capsule(capsule) => self.set_capsule(capsule),
+            Geometry::Segment(segment) => self.set_segment(segment),
+            Geometry::Polygon(polygon) => self.set_polygon(polygon),
+        }
+    }
+
+    pub fn set_circle(&self, circle: &Circle) -> Result<(), CreateError> {
+        let _scope = self.world.alloc_scope();
+        let id = self.geometry_id()?;
+        circle.validate()?;
+        unsafe { b2Shape_SetCircle(id, circle) };
+        self.update_body_mass()
+    }
+
+    /// A capsule with (nearly) coincident centers becomes a circle, the same as
+    /// when it is created with [Body::create_shape_capsule].
+    pub fn set_capsule(&self, capsule: &Capsule) -> Result<(), CreateError> {
+        let _scope = self.world.alloc_scope();
+        let id = self.geometry_id()?;
+        capsule.validate()?;
+        // unlike b2CreateCapsuleShape, b2Shape_SetCapsule has no fallback and would
+        // store a zero length capsule
+        if (capsule.center2 - capsule.center1).length() <= LINEAR_SLOP {
+            let circle = Circle {
+                center: (capsule.center1 + capsule.center2) * 0.5,
+                radius: capsule.radius,
+            };
+            unsafe { b2Shape_SetCircle(id, &circle) };
+        } else {
+            unsafe { b2Shape_SetCapsule(id, capsule) };
+        }
+        self.update_body_mass()
+    }
+
+    pub fn set_segment(&self, segment: &Segment) -> Result<(), CreateError> {
+        let _scope = self.world.alloc_scope();
+        let id = self.geometry_id()?;
+        segment.validate()?;
+        unsafe { b2Shape_SetSegment(id, segment) };
+        self.update_body_mass()
+    }
+
+    pub fn set_polygon(&self, polygon: &Polygon) -> Result<(), CreateError> {
+        let _scope = self.world.alloc_scope();
+        let id = self.geometry_id()?;
+        polygon.validate()?;
+        unsafe { b2Shape_SetPolygon(id, polygon) };
+        self.update_body_mass()
+    }
+
+    /// Get the raw id for changing geometry. Chain segments are rejected, since
+    /// Box2D would turn them into a shape the chain no longer knows about.
+    fn geometry_id(&self) -> Result<ShapeId, CreateError> {
+        let id = self.checked_id()?;
+        if unsafe { b2Shape_GetType(id) } == ShapeType::SmoothSegment {
+            return Err(CreateError::ChainSegment);
+        }
+        Ok(id)
+    }
+
+    /// Box2D recomputes the body's mass when shapes are added or removed, but not
+    /// when their geometry changes, so do it here for bodies with automatic mass.
+    fn update_body_mass(&self) -> Result<(), CreateError> {
+        let body = self.body()?;
+        if body.has_automatic_mass()? {
+            body.apply_mass_from_shapes()?;
+        }
+        Ok(())
+    }
+
+    /// Is a world point inside this shape?
+    pub fn test_point(&self, point: Vec2) -> Result<bool, StaleHandle> {
+        Ok(unsafe { b2Shape_TestPoint(self.checked_id()?, point) })
+    }
+
+    /// Cast a ray against this shape alone, in world coordinates. The output's
+    /// `fraction` is relative to `translation`.
+    pub fn ray_cast(&self, origin: Vec2, translation: Vec2) -> Result<CastOutput, StaleHandle> {
+        Ok(unsafe { b2Shape_RayCast(self.checked_id()?, origin, translation) })
+    }
+
+    /// The point on the shape closest to a world point. Points inside the
+    /// shape are returned as is.
+    pub fn closest_point(&self, target: Vec2) -> Result<Vec2, StaleHandle> {
+        Ok(unsafe { b2Shape_GetClosestPoint(self.checked_id()?, target) })
+    }
+
+    /// The world bounding box of the shape, as of the last step or move.
+    pub fn aabb(&self) -> Result<AABB, StaleHandle> {
+        Ok(unsafe { b2Shape_GetAABB(self.checked_id()?) })
+    }
+
+    /// Every touching contact on this shape, as of the last step.
+    pub fn contact_data(&self) -> Result<Vec<ContactData>, StaleHandle> {
+        let id = self.checked_id()?;
+        let capacity = unsafe { b2Shape_GetContactCapacity(id) };
+        Ok(collect_contacts(capacity, |buffer, capacity| unsafe {
+            b2Shape_GetContactData(id, buffer, capacity)
+        }))
+    }
+
+    /// Destroy this shape. If the body uses automatic mass, its mass is
+    /// recomputed from the remaining shapes. Other handles to the shape become stale.
+    ///
+    /// Chain segments belong to their chain and must be destroyed with [Chain::destroy].
+    ///
+    /// # Panics
+    ///
+    /// If the user data of any shape in the world is borrowed, see [Shape::user_data].
+    pub fn destroy(self) -> Result<(), CreateError> {
+        let id = self.checked_id()?;
+        if self.chain()?.is_some() {
+            return Err(CreateError::ChainSegment);
+        }
+        let mut shape_data = self.world.shape_registry().borrow_mut();
+        {
+            let _scope = self.world.alloc_scope();
+            unsafe {
+                b2DestroyShape(id);
+            }
+        }
+        shape_data.remove(&id);
+        Ok(())
+    }
+
+    /// Borrow the user data attached to this shape. While the borrow is held,
+    /// creating or destroying shapes, or changing their user data, panics.
+    pub fn user_data(&self) -> Result<Option<Ref<'w, S>>, StaleHandle> {
+        let id = self.checked_id()?;
+        let registry = self.world.shape_registry().borrow();
+        Ok(Ref::filter_map(registry, |registry| registry.get(&id)).ok())
+    }
+
+    /// Attach user data to this shape, dropping any previous value.
+    ///
+    /// # Panics
+    ///
+    /// If the user data of any shape in the world is borrowed, see [Shape::user_data].
+    pub fn set_user_data(&self, data: S) -> Result<(), StaleHandle> {
+        let id = self.checked_id()?;
+        self.world.shape_registry().borrow_mut().set(id, Some(data));
+        Ok(())
+    }
+
+    /// Drop the user data attached to this shape.
+    ///
+    /// # Panics
+    ///
+    /// If the user data of any shape in the world is borrowed, see [Shape::user_data].
+    pub fn clear_user_data(&self) -> Result<(), StaleHandle> {
+        let id = self.checked_id()?;
+        self.world.shape_registry().borrow_mut().set(id, None);
+        Ok(())
+    }
+}
+
+impl Geometry {
+    /// Check for geometry Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        match self {
+            Geometry::Circle(circle) => circle.validate(),
+            Geometry::Capsule(capsule) => capsule.validate(),
+            Geometry::Segment(segment) => segment.validate(),
+            Geometry::Polygon(polygon) => polygon.validate(),
+        }
+    }
+}
+
+impl MassData {
+    /// Check for mass properties Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        check_non_negative("mass", self.mass)?;
+        check_finite_vec("center", self.center)?;
+        check_non_negative("rotational_inertia", self.rotational_inertia)
+    }
+}
+
+impl Circle {
+    /// Check for geometry Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        check_finite_vec("center", self.center)?;
+        check_positive("radius", self.radius)
+    }
+
+    /// Compute mass properties of a circle with the given density.
+    pub fn compute_mass(&self, density: f32) -> MassData {
+        crate::assertion::install();
+        unsafe { b2ComputeCircleMass(self, density) }
+    }
+}
+
+impl Capsule {
+    /// Check for geometry Box2D would reject. Box2D turns a capsule with
+    /// (nearly) coincident centers into a circle, so that is allowed.
+    pub fn validate(&self) -> Result<(), DefError> {
+        check_finite_vec("center1", self.center1)?;
+        check_finite_vec("center2", self.center2)?;
+        check_positive("radius", self.radius)
+    }
+
+    /// Compute mass properties of a capsule with the given density.
+    pub fn compute_mass(&self, density: f32) -> MassData {
+        crate::assertion::install();
+        unsafe { b2ComputeCapsuleMass(self, density) }
+    }
+}
+
+impl Segment {
+    /// Check for geometry Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        check_finite_vec("point1", self.point1)?;
+        check_finite_vec("point2", self.point2)?;
+        if (self.point2 - self.point1).length() <= LINEAR_SLOP {
+            return Err(DefError::DegenerateSegment);
+        }
+        Ok(())
+    }
+
+    /// Cast a ray against this segment, in the segment's local space.
+    pub fn ray_cast(&self, input: &RayCastInput) -> CastOutput {
+        crate::assertion::install();
+        unsafe { b2RaySegment(input, self, false) }
+    }
+}
+
+impl RayCastInput {
+    /// A ray from `origin` covering all of `translation`.
+    pub fn new(origin: Vec2, translation: Vec2) -> Self {
+        Self {
+            origin,
+            translation,
+            max_fraction: 1.0,
+        }
+    }
+}
 
 impl Polygon {
+    /// Check for geometry Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        let count = self.vertex_count as usize;
+        // capsules made with new_capsule are rounded two-vertex polygons
+        let min_count = if self.radius > 0.0 { 2 } else { 3 };
+        if !(min_count..=MAX_POLYGON_VERTICES).contains(&count) {
+            return Err(DefError::DegeneratePolygon);
+        }
+        for v in &self.vertices[..count] {
+            check_finite_vec("vertices", *v)?;
+        }
+        check_non_negative("radius", self.radius)
+    }
+
     pub fn new_box(hx: f32, hy: f32) -> Self {
+        crate::assertion::install();
         unsafe { b2MakeBox(hx, hy) }
     }
 
     pub fn new_box_ex(hx: f32, hy: f32, center: Vec2, angle: f32) -> Self {
+        crate::assertion::install();
         unsafe { b2MakeOffsetBox(hx, hy, center, angle) }
     }
 
+    /// A square with half-width `h`.
+    pub fn new_square(h: f32) -> Result<Self, DefError> {
+        check_positive("h", h)?;
+        crate::assertion::install();
+        Ok(unsafe { b2MakeSquare(h) })
+    }
+
+    /// A box with its corners rounded off by `radius`, on top of the half-extents.
+    pub fn new_rounded_box(hx: f32, hy: f32, radius: f32) -> Result<Self, DefError> {
+        check_positive("hx", hx)?;
+        check_positive("hy", hy)?;
+        check_non_negative("radius", radius)?;
+        crate::assertion::install();
+        Ok(unsafe { b2MakeRoundedBox(hx, hy, radius) })
+    }
+
+    pub fn new_rounded_box_ex(
+        hx: f32,
+        hy: f32,
+        center: Vec2,
+        angle: f32,
+        radius: f32,
+    ) -> Result<Self, DefError> {
+        check_positive("hx", hx)?;
+        check_positive("hy", hy)?;
+        check_finite_vec("center", center)?;
+        check_finite("angle", angle)?;
+        check_non_negative("radius", radius)?;
+        crate::assertion::install();
+        Ok(unsafe { b2MakeOffsetRoundedBox(hx, hy, center, angle, radius) })
+    }
+
+    /// A copy of this polygon moved by `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        crate::assertion::install();
+        unsafe { b2TransformPolygon(transform.clone(), self) }
+    }
+
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices[..self.vertex_count as usize]
+    }
+
+    /// The outward normal of each side. Side `i` runs from vertex `i` to vertex `i + 1`.
+    pub fn normals(&self) -> &[Vec2] {
+        &self.normals[..self.vertex_count as usize]
+    }
+
+    pub fn centroid(&self) -> Vec2 {
+        self.centroid
+    }
+
+    /// The rounding radius, zero for sharp corners.
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
     pub fn new(hull: &Hull, radius: f32) -> Self {
+        crate::assertion::install();
         unsafe { b2MakePolygon(hull, radius) }
     }
+
+    /// A capsule as a rounded polygon, for when polygon collision is wanted.
+    /// Use [Capsule] for a real capsule shape. Unlike [Capsule], the centers
+    /// must be apart.
+    pub fn new_capsule(center1: Vec2, center2: Vec2, radius: f32) -> Result<Self, DefError> {
+        Capsule {
+            center1,
+            center2,
+            radius,
+        }
+        .validate()?;
+        if (center2 - center1).length() <= LINEAR_SLOP {
+            return Err(DefError::DegenerateCapsule);
+        }
+        crate::assertion::install();
+        Ok(unsafe { b2MakeCapsule(center1, center2, radius) })
+    }
+
+    /// Compute mass properties of a polygon with the given density.
+    pub fn compute_mass(&self, density: f32) -> MassData {
+        crate::assertion::install();
+        unsafe { b2ComputePolygonMass(self, density) }
+    }
 }
 
 impl Hull {
-    pub fn compute(points: &[Vec2]) -> Self {
-        // sanity check to avoid issues with the conversion to u32
-        assert!(points.len() < 1000);
-        unsafe { b2ComputeHull(points.as_ptr(), points.len() as u32) }
+    /// Compute the convex hull of up to 8 points. Points inside the hull or
+    /// on its edges are dropped.
+    pub fn compute(points: &[Vec2]) -> Result<Self, HullError> {
+        let result = Self::compute_unlogged(points);
+        #[cfg(feature = "log")]
+        if let Err(err) = &result {
+            log::warn!(
+                "failed to compute a hull from {} points: {}",
+                points.len(),
+                err
+            );
+        }
+        result
+    }
+
+    fn compute_unlogged(points: &[Vec2]) -> Result<Self, HullError> {
+        if !(3..=MAX_POLYGON_VERTICES).contains(&points.len()) {
+            return Err(HullError::PointCount(points.len()));
+        }
+        if points.iter().any(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            return Err(HullError::NotFinite);
+        }
+
+        crate::assertion::install();
+        let hull = unsafe { b2ComputeHull(points.as_ptr(), points.len() as u32) };
+        if hull.point_count == 0 || !unsafe { b2ValidateHull(&hull) } {
+            return Err(HullError::Degenerate);
+        }
+        Ok(hull)
+    }
+
+    pub fn points(&self) -> &[Vec2] {
+        &self.points[..self.point_count as usize]
     }
 }
 
-extern "C" {
+extern "C-unwind" {
     fn b2DefaultShapeDef() -> ShapeDef;
 
+    fn b2Shape_IsValid(shape: ShapeId) -> bool;
+    fn b2DestroyShape(shape: ShapeId);
+    fn b2Shape_GetBody(shape: ShapeId) -> BodyId;
+    fn b2Shape_GetParentChain(shape: ShapeId) -> ChainId;
+    fn b2Shape_GetType(shape: ShapeId) -> ShapeType;
+    fn b2Shape_TestPoint(shape: ShapeId, point: Vec2) -> bool;
+    fn b2Shape_RayCast(shape: ShapeId, origin: Vec2, translation: Vec2) -> CastOutput;
+    fn b2Shape_GetClosestPoint(shape: ShapeId, target: Vec2) -> Vec2;
+    fn b2Shape_GetAABB(shape: ShapeId) -> AABB;
+    fn b2Shape_GetCircle(shape: ShapeId) -> Circle;
+    fn b2Shape_GetCapsule(shape: ShapeId) -> Capsule;
+    fn b2Shape_GetSegment(shape: ShapeId) -> Segment;
+    fn b2Shape_GetPolygon(shape: ShapeId) -> Polygon;
+    fn b2Shape_SetCircle(shape: ShapeId, circle: &Circle);
+    fn b2Shape_SetCapsule(shape: ShapeId, capsule: &Capsule);
+    fn b2Shape_SetSegment(shape: ShapeId, segment: &Segment);
+    fn b2Shape_SetPolygon(shape: ShapeId, polygon: &Polygon);
+    fn b2Shape_GetFriction(shape: ShapeId) -> f32;
+    fn b2Shape_SetFriction(shape: ShapeId, friction: f32);
+    fn b2Shape_GetRestitution(shape: ShapeId) -> f32;
+    fn b2Shape_SetRestitution(shape: ShapeId, restitution: f32);
+    fn b2Shape_GetDensity(shape: ShapeId) -> f32;
+    fn b2Shape_SetDensity(shape: ShapeId, density: f32);
+    fn b2Shape_GetFilter(shape: ShapeId) -> Filter;
+    fn b2Shape_SetFilter(shape: ShapeId, filter: Filter);
+    fn b2Shape_IsSensor(shape: ShapeId) -> bool;
+    fn b2Shape_AreSensorEventsEnabled(shape: ShapeId) -> bool;
+    fn b2Shape_EnableSensorEvents(shape: ShapeId, flag: bool);
+    fn b2Shape_AreContactEventsEnabled(shape: ShapeId) -> bool;
+    fn b2Shape_EnableContactEvents(shape: ShapeId, flag: bool);
+    fn b2Shape_AreHitEventsEnabled(shape: ShapeId) -> bool;
+    fn b2Shape_EnableHitEvents(shape: ShapeId, flag: bool);
+    fn b2Shape_ArePreSolveEventsEnabled(shape: ShapeId) -> bool;
+    fn b2Shape_EnablePreSolveEvents(shape: ShapeId, flag: bool);
+    fn b2Shape_GetContactCapacity(shape: ShapeId) -> i32;
+    fn b2Shape_GetContactData(shape: ShapeId, contacts: *mut ContactData, capacity: i32) -> i32;
+
     fn b2MakeBox(hx: f32, hy: f32) -> Polygon;
     fn b2MakeOffsetBox(hx: f32, hy: f32, center: Vec2, angle: f32) -> Polygon;
     fn b2MakePolygon(hull: &Hull, radius: f32) -> Polygon;
+    fn b2MakeSquare(h: f32) -> Polygon;
+    fn b2MakeRoundedBox(hx: f32, hy: f32, radius: f32) -> Polygon;
+    fn b2MakeOffsetRoundedBox(hx: f32, hy: f32, center: Vec2, angle: f32, radius: f32) -> Polygon;
+    fn b2TransformPolygon(transform: Transform, polygon: &Polygon) -> Polygon;
+    fn b2MakeCapsule(p1: Vec2, p2: Vec2, radius: f32) -> Polygon;
 
     fn b2ComputeHull(points: *const Vec2, count: u32) -> Hull;
+    fn b2ValidateHull(hull: &Hull) -> bool;
+
+    fn b2RaySegment(input: &RayCastInput, segment: &Segment, one_sided: bool) -> CastOutput;
+
+    fn b2ComputeCircleMass(circle: &Circle, density: f32) -> MassData;
+    fn b2ComputeCapsuleMass(capsule: &Capsule, density: f32) -> MassData;
+    fn b2ComputePolygonMass(polygon: &Polygon, density: f32) -> MassData;
 }
@@ -0,0 +1,207 @@
+//! A thread pool implementing Box2D's task callbacks, so that `worker_count`
+//! in [crate::WorldDef] actually spreads [crate::World::step] over threads.
+
+use std::{
+    any::Any,
+    collections::VecDeque,
+    ffi::c_void,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use crate::allocator::{AllocCounter, AllocScope};
+
+pub(crate) type TaskCallback = extern "C-unwind" fn(
+    start_index: i32,
+    end_index: i32,
+    worker_index: u32,
+    task_context: *mut c_void,
+);
+
+pub(crate) type EnqueueTaskCallback = extern "C-unwind" fn(
+    task: TaskCallback,
+    item_count: i32,
+    min_range: i32,
+    task_context: *mut c_void,
+    user_context: *mut c_void,
+) -> *mut c_void;
+
+pub(crate) type FinishTaskCallback =
+    extern "C-unwind" fn(user_task: *mut c_void, user_context: *mut c_void);
+
+/// One range of a task, run by whichever worker picks it up first.
+struct Job {
+    task: TaskCallback,
+    start: i32,
+    end: i32,
+    context: *mut c_void,
+    state: Arc<TaskState>,
+}
+
+// Box2D keeps the task context alive and untouched until the task is finished.
+unsafe impl Send for Job {}
+
+#[derive(Default)]
+struct Queue {
+    jobs: VecDeque<Job>,
+    shutdown: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    queue: Mutex<Queue>,
+    available: Condvar,
+}
+
+#[derive(Default)]
+struct TaskProgress {
+    remaining: usize,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+/// Tracks the jobs of one enqueued task. A pointer to this is what Box2D
+/// holds as the user task.
+#[derive(Default)]
+struct TaskState {
+    progress: Mutex<TaskProgress>,
+    done: Condvar,
+}
+
+/// Worker threads owned by a world. Each thread has a fixed worker index, which
+/// is what Box2D uses to pick per-thread scratch memory. Allocations made by the
+/// jobs are attributed to the world that owns the pool.
+pub(crate) struct TaskPool {
+    shared: Arc<Shared>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl TaskPool {
+    pub fn new(worker_count: u32, alloc_counter: &Arc<AllocCounter>) -> Self {
+        let shared = Arc::new(Shared::default());
+        let threads = (0..worker_count)
+            .map(|worker_index| {
+                let shared = shared.clone();
+                let alloc_counter = alloc_counter.clone();
+                thread::Builder::new()
+                    .name(format!("box2d-worker-{}", worker_index))
+                    .spawn(move || worker_loop(&shared, &alloc_counter, worker_index))
+                    .expect("failed to spawn Box2D worker thread")
+            })
+            .collect();
+
+        Self { shared, threads }
+    }
+
+    pub fn enqueue_task(&self) -> EnqueueTaskCallback {
+        enqueue_task
+    }
+
+    pub fn finish_task(&self) -> FinishTaskCallback {
+        finish_task
+    }
+
+    /// The `user_task_context` Box2D hands back to the callbacks.
+    pub fn context(&self) -> *mut c_void {
+        self as *const Self as *mut c_void
+    }
+}
+
+impl Drop for TaskPool {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap().shutdown = true;
+        self.shared.available.notify_all();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn worker_loop(shared: &Shared, alloc_counter: &Arc<AllocCounter>, worker_index: u32) {
+    loop {
+        let job = {
+            let mut queue = shared.queue.lock().unwrap();
+            loop {
+                if let Some(job) = queue.jobs.pop_front() {
+                    break job;
+                }
+                if queue.shutdown {
+                    return;
+                }
+                queue = shared.available.wait(queue).unwrap();
+            }
+        };
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _scope = AllocScope::enter(alloc_counter);
+            (job.task)(job.start, job.end, worker_index, job.context)
+        }));
+
+        let mut progress = job.state.progress.lock().unwrap();
+        progress.remaining -= 1;
+        if let Err(payload) = result {
+            progress.panic.get_or_insert(payload);
+        }
+        if progress.remaining == 0 {
+            job.state.done.notify_all();
+        }
+    }
+}
+
+extern "C-unwind" fn enqueue_task(
+    task: TaskCallback,
+    item_count: i32,
+    min_range: i32,
+    task_context: *mut c_void,
+    user_context: *mut c_void,
+) -> *mut c_void {
+    let pool = unsafe { &*(user_context as *const TaskPool) };
+
+    // Never run inline on the calling thread: some of Box2D's tasks wait on each
+    // other and need a worker of their own.
+    let item_count = item_count.max(0);
+    let min_range = min_range.max(1);
+    let max_chunks = (item_count + min_range - 1) / min_range;
+    let chunk_count = max_chunks.clamp(1, pool.threads.len().max(1) as i32);
+
+    let state = Arc::new(TaskState::default());
+    state.progress.lock().unwrap().remaining = chunk_count as usize;
+
+    {
+        let mut queue = pool.shared.queue.lock().unwrap();
+        for chunk in 0..chunk_count {
+            let start = item_count * chunk / chunk_count;
+            let end = item_count * (chunk + 1) / chunk_count;
+            queue.jobs.push_back(Job {
+                task,
+                start,
+                end,
+                context: task_context,
+                state: state.clone(),
+            });
+        }
+    }
+    pool.shared.available.notify_all();
+
+    Arc::into_raw(state) as *mut c_void
+}
+
+extern "C-unwind" fn finish_task(user_task: *mut c_void, _user_context: *mut c_void) {
+    if user_task.is_null() {
+        return;
+    }
+    let state = unsafe { Arc::from_raw(user_task as *const TaskState) };
+
+    let panic = {
+        let mut progress = state.progress.lock().unwrap();
+        while progress.remaining > 0 {
+            progress = state.done.wait(progress).unwrap();
+        }
+        progress.panic.take()
+    };
+
+    // carry a failed assertion on a worker over to the thread that called step
+    if let Some(payload) = panic {
+        panic::resume_unwind(payload);
+    }
+}
@@ -1,11 +1,28 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
 use crate::{
+    allocator::{AllocCounter, AllocScope, AllocStats},
+    body::BodyId,
+    builder::{BodyBuilder, SpawnedBody},
+    chain::{Chain, ChainId},
     debug_draw::{DebugDraw, DebugDrawOpaque},
+    error::{check_finite_vec, check_non_negative, check_positive, DefError},
     math::Vec2,
-    Body, BodyDef, PhantomNoSend,
+    registry::Registry,
+    shapes::ShapeId,
+    task::{EnqueueTaskCallback, FinishTaskCallback, TaskPool},
+    Body, BodyDef, Shape, StaleHandle,
 };
 
+/// Box2D keeps per-worker scratch memory for this many workers.
+pub const MAX_WORKERS: u32 = 64;
+
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WorldDef {
     /// Gravity vector. Box2D has no up-vector defined.
     pub gravity: Vec2,
@@ -44,16 +61,19 @@ pub struct WorldDef {
     /// Number of workers to use with the provided task system. Box2D performs best when using only
     ///	performance cores and accessing a single L2 cache. Efficiency cores and hyper-threading provide
     ///	little benefit and may even harm performance.
+    ///
+    /// When this is greater than one, the world starts its own pool of worker threads,
+    /// which lives as long as the world. At most [MAX_WORKERS].
     pub worker_count: u32,
 
     /// Function to spawn tasks
-    enqueue_task: *const std::ffi::c_void,
+    enqueue_task: Option<EnqueueTaskCallback>,
 
     /// Function to finish a task
-    finish_task: *const std::ffi::c_void,
+    finish_task: Option<FinishTaskCallback>,
 
     /// User context that is provided to enqueueTask and finishTask
-    user_task_context: *const std::ffi::c_void,
+    user_task_context: *mut std::ffi::c_void,
 
     /// Used internally to detect a valid definition. DO NOT SET.
     _cookie: u32,
@@ -65,48 +85,247 @@ impl Default for WorldDef {
     }
 }
 
+impl WorldDef {
+    /// Check the definition for values Box2D would reject.
+    pub fn validate(&self) -> Result<(), DefError> {
+        check_finite_vec("gravity", self.gravity)?;
+        check_non_negative("restitution_threshold", self.restitution_threshold)?;
+        check_non_negative("contact_pushout_velocity", self.contact_pushout_velocity)?;
+        check_non_negative("hit_event_threshold", self.hit_event_threshold)?;
+        check_non_negative("contact_hertz", self.contact_hertz)?;
+        check_non_negative("contact_damping_ratio", self.contact_damping_ratio)?;
+        check_non_negative("joint_hertz", self.joint_hertz)?;
+        check_non_negative("joint_damping_ratio", self.joint_damping_ratio)?;
+        check_positive("maximum_linear_velocity", self.maximum_linear_velocity)?;
+        if self.worker_count > MAX_WORKERS {
+            return Err(DefError::TooManyWorkers);
+        }
+        Ok(())
+    }
+}
+
 #[repr(C)]
-#[derive(Clone)]
-pub struct World {
-    index: u16,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct WorldId {
+    /// One-based, unlike the world index stored in body and shape ids.
+    pub(crate) index: u16,
     revision: u16,
-    _thread_unsafe: PhantomNoSend,
 }
 
-impl World {
-    pub fn new(def: &WorldDef) -> Self {
-        unsafe { b2CreateWorld(def) }
+/// Box2D keeps its worlds in a global array without any locking, so creating and
+/// destroying worlds has to be serialized. Everything else is per world.
+pub(crate) static WORLD_SLOTS: Mutex<()> = Mutex::new(());
+
+/// Owns a Box2D world. The world is destroyed when this is dropped, and
+/// every [Body] and [crate::Shape] handle borrows it, so handles cannot outlive it.
+///
+/// A world can be moved to another thread (it is `Send` when the user data is),
+/// but not shared between threads. Body and shape handles borrow the world, so
+/// they always stay on the thread that currently owns it.
+///
+/// `B` and `S` are the types of user data attached to bodies and shapes.
+/// User data is dropped when it is replaced or its body or shape is destroyed.
+pub struct World<B = (), S = ()> {
+    id: WorldId,
+    bodies: RefCell<Registry<BodyId, B>>,
+    shapes: RefCell<Registry<ShapeId, S>>,
+    /// The segment shapes of each chain, since Box2D can't list them.
+    chains: RefCell<BTreeMap<ChainId, Vec<ShapeId>>>,
+    alloc_counter: Arc<AllocCounter>,
+    /// Declared last so the workers are only shut down after the world is destroyed.
+    _task_pool: Option<Box<TaskPool>>,
+}
+
+impl<B, S> World<B, S> {
+    pub fn new(def: &WorldDef) -> Result<Self, DefError> {
+        def.validate()?;
+        crate::assertion::install();
+        let alloc_counter = Arc::new(AllocCounter::new());
+
+        let mut def = def.clone();
+        let task_pool = if def.worker_count > 1 && def.enqueue_task.is_none() {
+            let pool = Box::new(TaskPool::new(def.worker_count, &alloc_counter));
+            def.enqueue_task = Some(pool.enqueue_task());
+            def.finish_task = Some(pool.finish_task());
+            def.user_task_context = pool.context();
+            Some(pool)
+        } else {
+            None
+        };
+
+        let id = {
+            let _lock = WORLD_SLOTS.lock().unwrap_or_else(|e| e.into_inner());
+            let _scope = AllocScope::enter(&alloc_counter);
+            unsafe { b2CreateWorld(&def) }
+        };
+        Ok(Self {
+            id,
+            bodies: RefCell::new(Registry::new()),
+            shapes: RefCell::new(Registry::new()),
+            chains: RefCell::new(BTreeMap::new()),
+            alloc_counter,
+            _task_pool: task_pool,
+        })
     }
 
-    pub fn destroy(self) {
-        unsafe {
-            b2DestroyWorld(self);
+    pub fn is_valid(&self) -> bool {
+        unsafe { b2World_IsValid(self.id) }
+    }
+
+    /// Get a handle back from a stored id. Fails if the body was destroyed or
+    /// belongs to another live world.
+    ///
+    /// Ids only record which world slot they came from, and a new world can reuse
+    /// the slot of a dropped one. An id kept from a dropped world may then match a
+    /// different body in this world, so don't keep ids longer than their world.
+    pub fn body(&self, id: BodyId) -> Result<Body<'_, B, S>, StaleHandle> {
+        let body = Body::new(self, id);
+        if body.is_valid() {
+            Ok(body)
+        } else {
+            Err(StaleHandle)
         }
     }
 
-    pub fn step(&self, time_step: f32, substep_count: u32) {
+    /// Get a handle back from a stored id. Fails if the shape was destroyed or
+    /// belongs to another live world. See [World::body] for ids from dropped worlds.
+    pub fn shape(&self, id: ShapeId) -> Result<Shape<'_, B, S>, StaleHandle> {
+        let shape = Shape::new(self, id);
+        if shape.is_valid() {
+            Ok(shape)
+        } else {
+            Err(StaleHandle)
+        }
+    }
+
+    /// Get a handle back from a stored id. Fails if the chain was destroyed or
+    /// belongs to another live world. See [World::body] for ids from dropped worlds.
+    pub fn chain(&self, id: ChainId) -> Result<Chain<'_, B, S>, StaleHandle> {
+        let chain = Chain::new(self, id);
+        if chain.is_valid() {
+            Ok(chain)
+        } else {
+            Err(StaleHandle)
+        }
+    }
+
+    pub fn body_count(&self) -> usize {
+        self.bodies.borrow().len()
+    }
+
+    pub fn shape_count(&self) -> usize {
+        self.shapes.borrow().len()
+    }
+
+    /// Every body in the world, ordered by id.
+    pub fn bodies(&self) -> Vec<Body<'_, B, S>> {
+        let ids = self.bodies.borrow().keys();
+        ids.into_iter().map(|id| Body::new(self, id)).collect()
+    }
+
+    /// Every shape in the world, ordered by id.
+    pub fn shapes(&self) -> Vec<Shape<'_, B, S>> {
+        let ids = self.shapes.borrow().keys();
+        ids.into_iter().map(|id| Shape::new(self, id)).collect()
+    }
+
+    /// The first body, by id, whose user data matches `predicate`. Body user
+    /// data is borrowed while `predicate` runs, see [Body::user_data].
+    pub fn find_body(&self, mut predicate: impl FnMut(&B) -> bool) -> Option<Body<'_, B, S>> {
+        let id = self
+            .bodies
+            .borrow()
+            .entries()
+            .find(|(_, data)| predicate(data))
+            .map(|(id, _)| id)?;
+        Some(Body::new(self, id))
+    }
+
+    /// The first shape, by id, whose user data matches `predicate`. Shape user
+    /// data is borrowed while `predicate` runs, see [Shape::user_data].
+    pub fn find_shape(&self, mut predicate: impl FnMut(&S) -> bool) -> Option<Shape<'_, B, S>> {
+        let id = self
+            .shapes
+            .borrow()
+            .entries()
+            .find(|(_, data)| predicate(data))
+            .map(|(id, _)| id)?;
+        Some(Shape::new(self, id))
+    }
+
+    pub(crate) fn id(&self) -> WorldId {
+        self.id
+    }
+
+    /// Allocations Box2D made on behalf of this world, while creating, stepping or
+    /// destroying things through this wrapper. Only counted when an allocator
+    /// has been installed with [crate::allocator::set_allocator].
+    pub fn alloc_stats(&self) -> AllocStats {
+        self.alloc_counter.snapshot()
+    }
+
+    /// Attribute Box2D allocations on this thread to this world until the scope is dropped.
+    pub(crate) fn alloc_scope(&self) -> AllocScope {
+        AllocScope::enter(&self.alloc_counter)
+    }
+
+    pub(crate) fn body_registry(&self) -> &RefCell<Registry<BodyId, B>> {
+        &self.bodies
+    }
+
+    pub(crate) fn shape_registry(&self) -> &RefCell<Registry<ShapeId, S>> {
+        &self.shapes
+    }
+
+    pub(crate) fn chain_segments(&self) -> &RefCell<BTreeMap<ChainId, Vec<ShapeId>>> {
+        &self.chains
+    }
+
+    pub fn step(&mut self, time_step: f32, substep_count: u32) {
+        let _scope = self.alloc_scope();
         unsafe {
-            b2World_Step(self.clone(), time_step, substep_count);
+            b2World_Step(self.id, time_step, substep_count);
         }
     }
 
     pub fn debug_draw<C>(&self, draw_opts: &DebugDraw<C>) {
-        unsafe { b2World_Draw(self.clone(), draw_opts.as_opaque()) }
+        unsafe { b2World_Draw(self.id, draw_opts.as_opaque()) }
     }
 
-    pub fn create_body(&self, def: &BodyDef) -> Body {
-        unsafe { b2CreateBody(self.clone(), def) }
+    pub fn create_body(&self, def: &BodyDef) -> Result<Body<'_, B, S>, DefError> {
+        def.validate()?;
+        let _scope = self.alloc_scope();
+        let id = unsafe { b2CreateBody(self.id, def) };
+        self.bodies.borrow_mut().insert(id);
+        Ok(Body::new(self, id))
+    }
+
+    /// Create a body and all of its shapes in one go. Nothing is created if any
+    /// part of the builder is invalid.
+    pub fn spawn(&self, builder: BodyBuilder<B, S>) -> Result<SpawnedBody<'_, B, S>, DefError> {
+        builder.build(self)
+    }
+}
+
+impl<B, S> Drop for World<B, S> {
+    fn drop(&mut self) {
+        let _lock = WORLD_SLOTS.lock().unwrap_or_else(|e| e.into_inner());
+        let _scope = self.alloc_scope();
+        unsafe {
+            b2DestroyWorld(self.id);
+        }
     }
 }
 
-extern "C" {
+extern "C-unwind" {
     fn b2DefaultWorldDef() -> WorldDef;
 
-    fn b2CreateWorld(def: &WorldDef) -> World;
-    fn b2DestroyWorld(world: World);
+    fn b2CreateWorld(def: &WorldDef) -> WorldId;
+    fn b2DestroyWorld(world: WorldId);
+    fn b2World_IsValid(world: WorldId) -> bool;
 
-    fn b2World_Draw(world: World, debug_draw: *const DebugDrawOpaque);
-    fn b2World_Step(world: World, time_step: f32, substep_count: u32);
+    fn b2World_Draw(world: WorldId, debug_draw: *const DebugDrawOpaque);
+    fn b2World_Step(world: WorldId, time_step: f32, substep_count: u32);
 
-    fn b2CreateBody(world: World, def: &BodyDef) -> Body;
+    fn b2CreateBody(world: WorldId, def: &BodyDef) -> BodyId;
 }
@@ -1,4 +1,7 @@
-use box2d3::shapes::Circle;
+use box2d3::{
+    shapes::{Circle, Segment},
+    BodyBuilder,
+};
 
 use crate::renderer::Renderer;
 
@@ -11,60 +14,46 @@ pub struct Engine {
 impl Engine {
     pub fn new() -> Self {
         let world_def = box2d3::WorldDef::default();
-        let world = box2d3::World::new(&world_def);
+        let world = box2d3::World::new(&world_def).unwrap();
 
         Self { world }
     }
 }
 
-impl Drop for Engine {
-    fn drop(&mut self) {
-        self.world.clone().destroy();
-    }
-}
-
 impl super::Engine for Engine {
     fn add_body(&mut self, def: super::BodyDef) -> Result<(), UnsupportedError> {
-        let mut b2d_def = box2d3::BodyDef::default();
-        b2d_def.position = def.position;
-        b2d_def.linear_velocity = def.linear_velocity;
-        b2d_def.angular_velocity = def.angular_velocity;
-        b2d_def.kind = match def.kind {
+        let kind = match def.kind {
             super::BodyKind::Dynamic => box2d3::body::BodyKind::Dynamic,
             super::BodyKind::Static => box2d3::body::BodyKind::Static,
             super::BodyKind::Kinematic => box2d3::body::BodyKind::Kinematic,
         };
 
-        let new_body = self.world.create_body(&b2d_def);
+        let mut builder = BodyBuilder::new(kind)
+            .at(def.position)
+            .linear_velocity(def.linear_velocity)
+            .angular_velocity(def.angular_velocity)
+            .friction(def.friction)
+            .restitution(def.restitution);
+
         for shape in def.shapes {
-            match shape {
+            builder = match shape {
                 super::ShapeDef::Polygon(polygon) => {
-                    let hull = box2d3::shapes::Hull::compute(&polygon.vertices);
-
-                    let mut shape_def = box2d3::ShapeDef::default();
-                    shape_def.friction = def.friction;
-                    shape_def.restitution = def.restitution;
-
-                    let polygon = box2d3::shapes::Polygon::new(&hull, polygon.radius);
-
-                    new_body.create_shape_polygon(&shape_def, &polygon);
-                }
-                super::ShapeDef::Circle(circle) => {
-                    let mut shape_def = box2d3::ShapeDef::default();
-                    shape_def.friction = def.friction;
-                    shape_def.restitution = def.restitution;
-
-                    new_body.create_shape_circle(
-                        &shape_def,
-                        &Circle {
-                            center: circle.offset,
-                            radius: circle.radius,
-                        },
-                    );
+                    let hull = box2d3::shapes::Hull::compute(&polygon.vertices).unwrap();
+                    builder.with_polygon(box2d3::shapes::Polygon::new(&hull, polygon.radius))
                 }
-            }
+                super::ShapeDef::Circle(circle) => builder.with_circle(Circle {
+                    center: circle.offset,
+                    radius: circle.radius,
+                }),
+                super::ShapeDef::Segment(segment) => builder.with_segment(Segment {
+                    point1: segment.point1,
+                    point2: segment.point2,
+                }),
+            };
         }
 
+        self.world.spawn(builder).unwrap();
+
         Ok(())
     }
 
@@ -72,6 +61,14 @@ impl super::Engine for Engine {
         self.world.step(dt, steps);
     }
 
+    fn body_count(&self) -> Option<usize> {
+        Some(self.world.body_count())
+    }
+
+    fn shape_count(&self) -> Option<usize> {
+        Some(self.world.shape_count())
+    }
+
     fn draw(&mut self, render: &mut Renderer) {
         let draw_opts = box2d3::debug_draw::DebugDraw::<Renderer> {
             draw_solid_polygon: |xform, verts, vert_count, _radius, color, render| {
@@ -97,9 +94,21 @@ impl super::Engine for Engine {
             },
             draw_polygon: |_, _, _, _| println!("draw_polygon"),
             draw_circle: |_, _, _, _| println!("draw_circle"),
-            draw_capsule: |_, _, _, _, _| println!("draw_capsule"),
-            draw_solid_capsule: |_, _, _, _, _| println!("draw_solid_capsule"),
-            draw_segment: |_, _, _, _| println!("draw_segment"),
+            draw_capsule: |p1, p2, radius, color, render| {
+                let render = unsafe { render.as_mut().unwrap() };
+
+                render.draw_capsule(p1, p2, radius, color);
+            },
+            draw_solid_capsule: |p1, p2, radius, color, render| {
+                let render = unsafe { render.as_mut().unwrap() };
+
+                render.draw_capsule(p1, p2, radius, color);
+            },
+            draw_segment: |p1, p2, color, render| {
+                let render = unsafe { render.as_mut().unwrap() };
+
+                render.draw_segment(p1, p2, color);
+            },
             draw_transform: |_, _| println!("draw_transform"),
             draw_point: |_, _, _, _| println!("draw_point"),
             draw_string: |_, _, _| println!("draw_string"),
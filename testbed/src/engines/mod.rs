@@ -21,6 +21,16 @@ pub trait Engine {
     fn step(&mut self, dt: f32, steps: u32);
 
     fn draw(&mut self, render: &mut Renderer);
+
+    /// Number of bodies in the world, for engines that can count them.
+    fn body_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Number of shapes in the world, for engines that can count them.
+    fn shape_count(&self) -> Option<usize> {
+        None
+    }
 }
 
 pub struct Polygon {
@@ -33,9 +43,15 @@ pub struct Circle {
     offset: Vec2,
 }
 
+pub struct Segment {
+    point1: Vec2,
+    point2: Vec2,
+}
+
 pub enum ShapeDef {
     Polygon(Polygon),
     Circle(Circle),
+    Segment(Segment),
 }
 
 enum BodyKind {
@@ -104,18 +120,41 @@ impl Polygon {
     }
 
     pub fn rotate(mut self, degrees: f32) -> Self {
-        let rad = -degrees.to_radians();
-        let s = rad.sin();
-        let c = rad.cos();
         for vert in &mut self.vertices {
-            let x = vert.x * c - vert.y * s;
-            let y = vert.x * s + vert.y * c;
-            *vert = Vec2 { x, y };
+            *vert = rotate(*vert, degrees);
         }
         self
     }
 }
 
+impl Segment {
+    pub fn new(point1: Vec2, point2: Vec2) -> Self {
+        Self { point1, point2 }
+    }
+
+    pub fn offset(mut self, offset: Vec2) -> Self {
+        self.point1 += offset;
+        self.point2 += offset;
+        self
+    }
+
+    pub fn rotate(mut self, degrees: f32) -> Self {
+        self.point1 = rotate(self.point1, degrees);
+        self.point2 = rotate(self.point2, degrees);
+        self
+    }
+}
+
+/// Rotate clockwise around the origin.
+fn rotate(v: Vec2, degrees: f32) -> Vec2 {
+    let rad = -degrees.to_radians();
+    let s = rad.sin();
+    let c = rad.cos();
+    let x = v.x * c - v.y * s;
+    let y = v.x * s + v.y * c;
+    Vec2 { x, y }
+}
+
 impl Circle {
     pub fn new(radius: f32) -> Self {
         Circle {
@@ -136,3 +175,9 @@ impl From<Circle> for ShapeDef {
         ShapeDef::Circle(inner_shape)
     }
 }
+
+impl From<Segment> for ShapeDef {
+    fn from(inner_shape: Segment) -> Self {
+        ShapeDef::Segment(inner_shape)
+    }
+}
@@ -58,6 +58,13 @@ impl super::Engine for Engine {
                         b2::CircleShape::new_with(convert_vec2(circle.offset), circle.radius);
                     body.create_fast_fixture(&circle, 1.0)
                 }
+                super::ShapeDef::Segment(segment) => {
+                    let edge = b2::EdgeShape::new_with(
+                        &convert_vec2(segment.point1),
+                        &convert_vec2(segment.point2),
+                    );
+                    body.create_fast_fixture(&edge, 1.0)
+                }
             };
             let mut fixture = body.fixture_mut(fh);
             fixture.set_friction(def.friction);
@@ -113,7 +120,11 @@ impl<'a> b2::Draw for DebugDrawer<'a> {
     }
 
     fn draw_segment(&mut self, p1: &b2::Vec2, p2: &b2::Vec2, color: &b2::Color) {
-        println!("segment");
+        self.render.draw_segment(
+            convert_vec2_back(*p1),
+            convert_vec2_back(*p2),
+            convert_color(color),
+        );
     }
 
     fn draw_transform(&mut self, xf: &b2::Transform) {
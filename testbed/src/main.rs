@@ -99,6 +99,9 @@ fn loop_inner(state: &mut State) {
         state.perf_info.step_time = 0.0;
     }
 
+    state.perf_info.body_count = state.engine.body_count();
+    state.perf_info.shape_count = state.engine.shape_count();
+
     state.render.clear(HexColor::new(0x111111));
 
     state.engine.draw(&mut state.render);
@@ -150,4 +153,6 @@ struct PerfInfo {
     step_time: f64,
     step_sum: f64,
     step_count: usize,
+    body_count: Option<usize>,
+    shape_count: Option<usize>,
 }
@@ -207,6 +207,29 @@ impl Renderer {
         }
     }
 
+    pub fn draw_capsule(&mut self, p1: Vec2, p2: Vec2, radius: f32, color: HexColor) {
+        let axis = p2 - p1;
+        let length = axis.length();
+        if length > 0.0 {
+            let side = Vec2::new(-axis.y, axis.x) * (radius / length);
+            self.draw_polygon(&[p1 + side, p1 - side, p2 - side, p2 + side], color);
+        }
+        self.draw_circle(p1, 0.0, radius, color);
+        self.draw_circle(p2, 0.0, radius, color);
+    }
+
+    /// Segments have no thickness, so they are drawn as thin quads.
+    pub fn draw_segment(&mut self, p1: Vec2, p2: Vec2, color: HexColor) {
+        const HALF_WIDTH: f32 = 0.25;
+
+        let axis = p2 - p1;
+        let length = axis.length();
+        if length > 0.0 {
+            let side = Vec2::new(-axis.y, axis.x) * (HALF_WIDTH / length);
+            self.draw_polygon(&[p1 + side, p1 - side, p2 - side, p2 + side], color);
+        }
+    }
+
     /// Draw shape geometry buffered by other calls
     pub fn draw_buffered_shapes(&mut self, scale: f32) {
         let gl = self.imgui_render.gl_context();
@@ -268,6 +291,12 @@ impl Renderer {
                 format!("{:.2} ms", perf_info.step_sum / perf_info.step_count as f64),
             );
             ui.label_text("Step Count", format!("{}", perf_info.step_count));
+            if let Some(body_count) = perf_info.body_count {
+                ui.label_text("Bodies", format!("{}", body_count));
+            }
+            if let Some(shape_count) = perf_info.shape_count {
+                ui.label_text("Shapes", format!("{}", shape_count));
+            }
 
             ui.input_int("Stop at Step", stop_step).build();
 
@@ -1,4 +1,4 @@
-use crate::engines::{BodyDef, Circle, Engine, Polygon};
+use crate::engines::{BodyDef, Circle, Engine, Polygon, Segment};
 use crate::Vec2;
 
 pub static TESTS: &[(&str, fn(&mut dyn Engine))] = &[
@@ -44,7 +44,7 @@ pub fn test_mat_props(engine: &mut dyn Engine) {
             .add_body(
                 BodyDef::new(
                     Vec2::new(0.0, 0.0),
-                    vec![Polygon::new_box(40.0, 1.0)
+                    vec![Segment::new(Vec2::new(-20.0, 0.0), Vec2::new(20.0, 0.0))
                         .offset(Vec2::new(0.0, -100.0))
                         .rotate(i as f32 * 5.0)
                         .offset(Vec2::new(60.0, 80.0))
@@ -58,7 +58,7 @@ pub fn test_mat_props(engine: &mut dyn Engine) {
         .add_body(
             BodyDef::new(
                 Vec2::new(0.0, 0.0),
-                vec![Polygon::new_box(40.0, 1.0)
+                vec![Segment::new(Vec2::new(-20.0, 0.0), Vec2::new(20.0, 0.0))
                     .offset(Vec2::new(-50.0, -40.0))
                     .into()],
             )
@@ -0,0 +1,159 @@
+//! The allocator is global to the process, so these tests get their own binary
+//! and run one at a time.
+
+use std::{
+    alloc::Layout,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, Once,
+    },
+};
+
+use box2d3::{
+    allocator::{self, AllocStats, Allocator, RustAllocator},
+    shapes::Polygon,
+    BodyBuilder, Vec2, World, WorldDef,
+};
+
+mod common;
+
+/// Forwards to the Rust allocator and counts the calls.
+struct Counting {
+    allocs: AtomicUsize,
+    frees: AtomicUsize,
+}
+
+impl Allocator for Counting {
+    fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.allocs.fetch_add(1, Ordering::Relaxed);
+        RustAllocator.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.frees.fetch_add(1, Ordering::Relaxed);
+        RustAllocator.dealloc(ptr, layout)
+    }
+}
+
+static COUNTING: Counting = Counting {
+    allocs: AtomicUsize::new(0),
+    frees: AtomicUsize::new(0),
+};
+static INSTALL: Once = Once::new();
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn install() {
+    INSTALL.call_once(|| {
+        assert_eq!(allocator::byte_count(), 0);
+        allocator::set_allocator(&COUNTING).unwrap();
+    });
+}
+
+/// Box2D rounds every allocation up to 32 bytes before it reaches the allocator,
+/// but only counts the size it asked for.
+fn assert_consistent(stats: AllocStats) {
+    let byte_count = allocator::byte_count();
+    assert!(stats.bytes >= byte_count, "{:?} < {}", stats, byte_count);
+    assert!(stats.bytes - byte_count < 32 * stats.live_allocations.max(1));
+    assert_eq!(stats.bytes == 0, byte_count == 0);
+}
+
+fn spawn_stack(world: &World) {
+    world
+        .spawn(BodyBuilder::static_body().with_polygon(Polygon::new_box(10.0, 0.5)))
+        .unwrap();
+    for i in 0..20 {
+        world
+            .spawn(
+                BodyBuilder::dynamic()
+                    .at(Vec2::new(0.0, 1.0 + i as f32))
+                    .with_polygon(Polygon::new_box(0.5, 0.5)),
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+fn world_allocations_are_counted() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    install();
+    let before = allocator::global_stats();
+    let allocs_before = COUNTING.allocs.load(Ordering::Relaxed);
+
+    let mut world: World = common::world();
+    let created = world.alloc_stats();
+    assert!(created.bytes > 0);
+    assert!(created.live_allocations > 0);
+    assert!(COUNTING.allocs.load(Ordering::Relaxed) > allocs_before);
+
+    spawn_stack(&world);
+    common::run(&mut world, 30);
+    let stepped = world.alloc_stats();
+    assert!(stepped.total_allocations > created.total_allocations);
+    assert!(stepped.peak_bytes >= stepped.bytes);
+    assert!(stepped.peak_bytes > created.bytes);
+
+    let global = allocator::global_stats();
+    assert!(global.bytes >= before.bytes + stepped.bytes);
+    assert_consistent(global);
+
+    drop(world);
+    let after = allocator::global_stats();
+    assert_eq!(after.bytes, before.bytes);
+    assert_eq!(after.live_allocations, before.live_allocations);
+    assert_consistent(after);
+}
+
+#[test]
+fn worlds_are_counted_separately() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    install();
+
+    let busy: World = common::world();
+    let idle: World = common::world();
+    let idle_before = idle.alloc_stats();
+
+    spawn_stack(&busy);
+    assert!(busy.alloc_stats().total_allocations > idle_before.total_allocations);
+    assert_eq!(idle.alloc_stats(), idle_before);
+}
+
+#[test]
+fn worker_allocations_are_counted() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    install();
+    let before = allocator::global_stats();
+
+    {
+        let mut def = WorldDef::default();
+        def.worker_count = 4;
+        let mut world: World = World::new(&def).unwrap();
+        spawn_stack(&world);
+        common::run(&mut world, 30);
+        let global = allocator::global_stats();
+        // everything Box2D holds right now belongs to this world
+        assert_eq!(global.bytes - before.bytes, world.alloc_stats().bytes);
+        assert_consistent(global);
+    }
+
+    assert_eq!(allocator::global_stats().bytes, before.bytes);
+    assert_eq!(allocator::byte_count(), 0);
+}
+
+#[test]
+fn allocator_can_be_swapped_while_worlds_exist() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    install();
+
+    let mut world: World = common::world();
+    spawn_stack(&world);
+    allocator::set_allocator(&RustAllocator).unwrap();
+    common::run(&mut world, 10);
+    // blocks from the first allocator are still freed by it
+    let frees = COUNTING.frees.load(Ordering::Relaxed);
+    drop(world);
+    assert!(COUNTING.frees.load(Ordering::Relaxed) > frees);
+    assert_eq!(allocator::byte_count(), 0);
+
+    allocator::set_allocator(&COUNTING).unwrap();
+}
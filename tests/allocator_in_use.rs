@@ -0,0 +1,23 @@
+//! Box2D already holds memory from the C allocator by the time the allocator
+//! is set here, so this needs its own process.
+
+use box2d3::{allocator, allocator::RustAllocator, AllocatorInUse, World};
+
+mod common;
+
+#[test]
+fn allocator_is_rejected_once_box2d_has_allocated() {
+    let world: World = common::world();
+    assert!(allocator::byte_count() > 0);
+    assert_eq!(
+        allocator::set_allocator(&RustAllocator),
+        Err(AllocatorInUse)
+    );
+
+    // nothing is left allocated once the world is gone, so it can be set now
+    drop(world);
+    assert_eq!(allocator::byte_count(), 0);
+    allocator::set_allocator(&RustAllocator).unwrap();
+    let world: World = common::world();
+    assert!(world.alloc_stats().bytes > 0);
+}
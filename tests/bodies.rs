@@ -0,0 +1,123 @@
+use box2d3::{math::Rot, shapes::MassData, BodyBuilder, CreateError, DefError, Vec2, World};
+
+mod common;
+
+#[test]
+fn body_state_setters_reject_bad_values() {
+    let world: World = common::world();
+    let body = common::dynamic_body(&world);
+
+    assert_eq!(
+        body.set_sleep_threshold(-1.0),
+        Err(CreateError::InvalidDef(DefError::Negative(
+            "sleep_threshold"
+        )))
+    );
+    assert_eq!(
+        body.set_linear_damping(f32::NAN),
+        Err(CreateError::InvalidDef(DefError::NotFinite(
+            "linear_damping"
+        )))
+    );
+    assert_eq!(
+        body.set_angular_damping(-0.5),
+        Err(CreateError::InvalidDef(DefError::Negative(
+            "angular_damping"
+        )))
+    );
+    assert_eq!(
+        body.set_gravity_scale(f32::INFINITY),
+        Err(CreateError::InvalidDef(DefError::NotFinite(
+            "gravity_scale"
+        )))
+    );
+
+    body.set_sleep_threshold(0.1).unwrap();
+    body.set_linear_damping(0.5).unwrap();
+    body.set_angular_damping(0.25).unwrap();
+    // negative gravity scales are allowed, they make a body float
+    body.set_gravity_scale(-1.0).unwrap();
+    assert_eq!(body.sleep_threshold().unwrap(), 0.1);
+    assert_eq!(body.linear_damping().unwrap(), 0.5);
+    assert_eq!(body.angular_damping().unwrap(), 0.25);
+    assert_eq!(body.gravity_scale().unwrap(), -1.0);
+}
+
+#[test]
+fn transform_and_velocity_setters_reject_bad_values() {
+    let world: World = common::world();
+    let body = common::dynamic_body(&world);
+
+    assert_eq!(
+        body.set_transform(Vec2::new(f32::NAN, 0.0), Rot::identity()),
+        Err(CreateError::InvalidDef(DefError::NotFinite("position")))
+    );
+    assert_eq!(
+        body.set_transform(Vec2::ZERO, Rot { cos: 2.0, sin: 0.0 }),
+        Err(CreateError::InvalidDef(DefError::RotationNotNormalized))
+    );
+    assert_eq!(
+        body.set_linear_velocity(Vec2::new(0.0, f32::INFINITY)),
+        Err(CreateError::InvalidDef(DefError::NotFinite(
+            "linear_velocity"
+        )))
+    );
+    assert_eq!(
+        body.set_angular_velocity(f32::NAN),
+        Err(CreateError::InvalidDef(DefError::NotFinite(
+            "angular_velocity"
+        )))
+    );
+
+    body.set_transform(Vec2::new(1.0, 2.0), Rot::from_angle(0.5))
+        .unwrap();
+    body.set_linear_velocity(Vec2::new(3.0, 0.0)).unwrap();
+    body.set_angular_velocity(1.5).unwrap();
+    assert_eq!(body.position().unwrap(), Vec2::new(1.0, 2.0));
+    assert!((body.rotation().unwrap().angle() - 0.5).abs() < 1e-5);
+    assert_eq!(body.linear_velocity().unwrap(), Vec2::new(3.0, 0.0));
+    assert_eq!(body.angular_velocity().unwrap(), 1.5);
+}
+
+#[test]
+fn mass_data_is_validated() {
+    let world: World = common::world();
+    let body = world
+        .spawn(BodyBuilder::dynamic().automatic_mass(false))
+        .unwrap()
+        .body;
+    let mass_data = MassData {
+        mass: 2.0,
+        center: Vec2::new(0.5, 0.0),
+        rotational_inertia: 1.0,
+    };
+
+    assert_eq!(
+        body.set_mass_data(MassData {
+            mass: -1.0,
+            ..mass_data
+        }),
+        Err(CreateError::InvalidDef(DefError::Negative("mass")))
+    );
+    assert_eq!(
+        body.set_mass_data(MassData {
+            center: Vec2::new(f32::NAN, 0.0),
+            ..mass_data
+        }),
+        Err(CreateError::InvalidDef(DefError::NotFinite("center")))
+    );
+    assert_eq!(
+        body.set_mass_data(MassData {
+            rotational_inertia: f32::NAN,
+            ..mass_data
+        }),
+        Err(CreateError::InvalidDef(DefError::NotFinite(
+            "rotational_inertia"
+        )))
+    );
+    assert_eq!(body.mass().unwrap(), 0.0);
+
+    body.set_mass_data(mass_data).unwrap();
+    assert_eq!(body.mass().unwrap(), 2.0);
+    assert_eq!(body.local_center_of_mass().unwrap(), Vec2::new(0.5, 0.0));
+}
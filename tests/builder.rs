@@ -0,0 +1,70 @@
+use box2d3::{
+    shapes::{Polygon, Segment},
+    BodyBuilder, DefError, ShapeDef, Vec2, World,
+};
+
+mod common;
+
+#[test]
+fn cloned_builder_spawns_copies() {
+    let world: World = common::world();
+    let crate_prefab = BodyBuilder::dynamic()
+        .density(2.0)
+        .with_polygon(Polygon::new_box(0.5, 0.5))
+        .with_circle(common::ball(0.5));
+
+    for i in 0..3 {
+        let spawned = world
+            .spawn(crate_prefab.clone().at(Vec2::new(i as f32 * 2.0, 0.0)))
+            .unwrap();
+        assert_eq!(spawned.shapes.len(), 2);
+        assert_eq!(
+            spawned.body.position().unwrap(),
+            Vec2::new(i as f32 * 2.0, 0.0)
+        );
+        assert_eq!(
+            spawned.shapes[1].geometry().unwrap(),
+            Some(common::ball(0.5).into())
+        );
+    }
+    assert_eq!(world.body_count(), 3);
+    assert_eq!(world.shape_count(), 6);
+}
+
+#[test]
+fn nothing_is_spawned_when_a_shape_is_invalid() {
+    let world: World = common::world();
+    let point = Vec2::new(1.0, 0.0);
+
+    let bad_geometry = BodyBuilder::dynamic()
+        .with_circle(common::ball(0.5))
+        .with_segment(Segment {
+            point1: point,
+            point2: point,
+        });
+    assert_eq!(
+        world.spawn(bad_geometry).err(),
+        Some(DefError::DegenerateSegment)
+    );
+
+    let mut bad_def = ShapeDef::default();
+    bad_def.friction = -1.0;
+    let bad_shape_def = BodyBuilder::dynamic()
+        .with_circle(common::ball(0.5))
+        .with_shape(bad_def, common::ball(0.5), None);
+    assert_eq!(
+        world.spawn(bad_shape_def).err(),
+        Some(DefError::Negative("friction"))
+    );
+
+    let bad_builder_def = BodyBuilder::dynamic()
+        .friction(f32::NAN)
+        .with_circle(common::ball(0.5));
+    assert_eq!(
+        world.spawn(bad_builder_def).err(),
+        Some(DefError::NotFinite("friction"))
+    );
+
+    assert_eq!(world.body_count(), 0);
+    assert_eq!(world.shape_count(), 0);
+}
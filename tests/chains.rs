@@ -0,0 +1,193 @@
+use box2d3::{
+    chain::ChainDef,
+    shapes::{Geometry, Segment, ShapeType},
+    BodyBuilder, CreateError, DefError, Vec2, World,
+};
+
+mod common;
+
+/// A bumpy floor running right to left, so its solid side faces up.
+fn terrain() -> Vec<Vec2> {
+    (0..8)
+        .map(|i| Vec2::new(7.0 - i as f32 * 2.0, (i % 2) as f32 * 0.1))
+        .collect()
+}
+
+/// A counter-clockwise box, so the solid side faces out.
+fn outline() -> Vec<Vec2> {
+    vec![
+        Vec2::new(-2.0, -1.0),
+        Vec2::new(2.0, -1.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(-2.0, 1.0),
+    ]
+}
+
+#[test]
+fn open_chain_uses_end_points_as_ghosts() {
+    let world: World = common::world();
+    let body = common::static_body(&world);
+    let points = terrain();
+    let chain = body
+        .create_chain(&ChainDef::new(points.clone(), false))
+        .unwrap();
+
+    let segments = chain.segments().unwrap();
+    assert_eq!(segments.len(), points.len() - 3);
+    assert_eq!(world.shape_count(), segments.len());
+    assert_eq!(chain.body().unwrap().id(), body.id());
+
+    for (i, segment) in segments.iter().enumerate() {
+        assert_eq!(segment.shape_type().unwrap(), ShapeType::SmoothSegment);
+        assert_eq!(segment.chain().unwrap().unwrap().id(), chain.id());
+        assert!(segment.geometry().unwrap().is_none());
+
+        // segment i runs between points i + 1 and i + 2
+        let aabb = segment.aabb().unwrap();
+        assert!((aabb.lower_bound.x - points[i + 2].x).abs() < 0.2);
+        assert!((aabb.upper_bound.x - points[i + 1].x).abs() < 0.2);
+    }
+}
+
+#[test]
+fn looped_chain_has_a_segment_per_point() {
+    let world: World = common::world();
+    let body = common::static_body(&world);
+    let chain = body.create_chain(&ChainDef::new(outline(), true)).unwrap();
+
+    assert_eq!(chain.segments().unwrap().len(), 4);
+    assert_eq!(world.chain(chain.id()).unwrap().id(), chain.id());
+}
+
+#[test]
+fn destroying_a_chain_removes_its_segments() {
+    let world: World = common::world();
+    let body = common::static_body(&world);
+    let other = body
+        .create_shape_circle(&Default::default(), &common::ball(0.5))
+        .unwrap();
+    let chain = body.create_chain(&ChainDef::new(outline(), true)).unwrap();
+    let segments = chain.segments().unwrap();
+    let chain_id = chain.id();
+
+    chain.destroy().unwrap();
+    assert!(world.chain(chain_id).is_err());
+    assert!(segments.iter().all(|segment| !segment.is_valid()));
+    assert_eq!(world.shape_count(), 1);
+    assert_eq!(body.shape_count().unwrap(), 1);
+    assert!(other.is_valid());
+}
+
+#[test]
+fn chain_materials_apply_to_every_segment() {
+    let world: World = common::world();
+    let body = common::static_body(&world);
+    let chain = body.create_chain(&ChainDef::new(outline(), true)).unwrap();
+
+    assert_eq!(
+        chain.set_friction(-1.0),
+        Err(CreateError::InvalidDef(DefError::Negative("friction")))
+    );
+    assert_eq!(
+        chain.set_restitution(f32::NAN),
+        Err(CreateError::InvalidDef(DefError::NotFinite("restitution")))
+    );
+
+    chain.set_friction(0.25).unwrap();
+    chain.set_restitution(0.5).unwrap();
+    for segment in chain.segments().unwrap() {
+        assert_eq!(segment.friction().unwrap(), 0.25);
+        assert_eq!(segment.restitution().unwrap(), 0.5);
+    }
+}
+
+#[test]
+fn chain_segments_can_not_be_destroyed_alone() {
+    let world: World = common::world();
+    let body = common::static_body(&world);
+    let chain = body.create_chain(&ChainDef::new(outline(), true)).unwrap();
+    let segment = chain.segments().unwrap()[0];
+
+    assert_eq!(segment.destroy(), Err(CreateError::ChainSegment));
+    assert!(segment.is_valid());
+    assert_eq!(chain.segments().unwrap().len(), 4);
+    chain.destroy().unwrap();
+}
+
+#[test]
+fn chain_segments_keep_their_geometry() {
+    let world: World = common::world();
+    let body = common::static_body(&world);
+    let chain = body.create_chain(&ChainDef::new(outline(), true)).unwrap();
+    let segment = chain.segments().unwrap()[0];
+    let circle = common::ball(0.5);
+
+    assert_eq!(segment.set_circle(&circle), Err(CreateError::ChainSegment));
+    assert_eq!(
+        segment.set_geometry(&Geometry::Segment(Segment {
+            point1: Vec2::ZERO,
+            point2: Vec2::new(1.0, 0.0),
+        })),
+        Err(CreateError::ChainSegment)
+    );
+    assert_eq!(segment.shape_type().unwrap(), ShapeType::SmoothSegment);
+}
+
+#[test]
+fn destroying_the_body_destroys_its_chains() {
+    let world: World = common::world();
+    let body = common::static_body(&world);
+    let chain = body.create_chain(&ChainDef::new(terrain(), false)).unwrap();
+
+    body.destroy().unwrap();
+    assert!(!chain.is_valid());
+    assert!(chain.segments().is_err());
+    assert_eq!(world.shape_count(), 0);
+}
+
+#[test]
+fn ball_rolls_along_open_chain() {
+    let mut world: World = common::world();
+    let body = common::static_body(&world);
+    body.create_chain(&ChainDef::new(terrain(), false)).unwrap();
+
+    let ball = world
+        .spawn(
+            BodyBuilder::dynamic()
+                .at(Vec2::new(-2.0, 1.0))
+                .linear_velocity(Vec2::new(2.0, 0.0))
+                .with_circle(common::ball(0.25)),
+        )
+        .unwrap()
+        .body
+        .id();
+
+    common::run(&mut world, 60);
+
+    let position = world.body(ball).unwrap().position().unwrap();
+    assert!(position.x > -2.0);
+    // held up by the chain instead of falling through it
+    assert!(position.y > 0.0);
+}
+
+#[test]
+fn ball_stays_outside_looped_chain() {
+    let mut world: World = common::world();
+    let body = common::static_body(&world);
+    body.create_chain(&ChainDef::new(outline(), true)).unwrap();
+
+    let ball = world
+        .spawn(
+            BodyBuilder::dynamic()
+                .at(Vec2::new(0.0, 2.0))
+                .with_circle(common::ball(0.25)),
+        )
+        .unwrap()
+        .body
+        .id();
+
+    common::run(&mut world, 120);
+
+    let position = world.body(ball).unwrap().position().unwrap();
+    assert!((position.y - 1.25).abs() < 0.05, "{:?}", position);
+}
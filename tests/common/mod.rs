@@ -0,0 +1,50 @@
+//! Helpers shared by the integration tests. Each test binary only uses some of them.
+#![allow(dead_code)]
+
+use box2d3::{shapes::Circle, Body, BodyBuilder, Vec2, World, WorldDef};
+
+pub const DT: f32 = 1.0 / 60.0;
+
+pub fn assert_close(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+}
+
+pub fn assert_point(a: Vec2, b: Vec2) {
+    assert!((a - b).length() < 1e-3, "{:?} != {:?}", a, b);
+}
+
+/// A world with the default settings, gravity included.
+pub fn world<B, S>() -> World<B, S> {
+    World::new(&WorldDef::default()).unwrap()
+}
+
+/// A world without gravity, so only the applied forces move things.
+pub fn zero_gravity_world<B, S>() -> World<B, S> {
+    let mut def = WorldDef::default();
+    def.gravity = Vec2::ZERO;
+    World::new(&def).unwrap()
+}
+
+/// A static body at the origin without any shapes.
+pub fn static_body<B, S>(world: &World<B, S>) -> Body<'_, B, S> {
+    world.spawn(BodyBuilder::static_body()).unwrap().body
+}
+
+/// A dynamic body at the origin without any shapes.
+pub fn dynamic_body<B, S>(world: &World<B, S>) -> Body<'_, B, S> {
+    world.spawn(BodyBuilder::dynamic()).unwrap().body
+}
+
+pub fn ball(radius: f32) -> Circle {
+    Circle {
+        center: Vec2::ZERO,
+        radius,
+    }
+}
+
+/// Step the world `count` times with [DT] and 4 substeps.
+pub fn run<B, S>(world: &mut World<B, S>, count: usize) {
+    for _ in 0..count {
+        world.step(DT, 4);
+    }
+}
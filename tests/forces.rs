@@ -0,0 +1,103 @@
+use box2d3::{shapes::Polygon, BodyBuilder, Vec2, World};
+
+mod common;
+
+use common::{assert_close, DT};
+
+/// A 1x1 box with density 1, which makes its mass 1.
+fn unit_box() -> BodyBuilder {
+    BodyBuilder::dynamic()
+        .density(1.0)
+        .with_polygon(Polygon::new_box(0.5, 0.5))
+}
+
+#[test]
+fn linear_impulse_sets_momentum() {
+    let mut world: World = common::zero_gravity_world();
+    let id = world.spawn(unit_box()).unwrap().body.id();
+
+    world
+        .body(id)
+        .unwrap()
+        .apply_linear_impulse_to_center(Vec2::new(2.0, -1.0), true)
+        .unwrap();
+    world.step(DT, 4);
+
+    let body = world.body(id).unwrap();
+    let velocity = body.linear_velocity().unwrap();
+    assert_close(velocity.x, 2.0);
+    assert_close(velocity.y, -1.0);
+    assert!(body.position().unwrap().x > 0.0);
+    assert_close(body.angular_velocity().unwrap(), 0.0);
+}
+
+#[test]
+fn force_accelerates_over_a_step() {
+    let mut world: World = common::zero_gravity_world();
+    let id = world.spawn(unit_box()).unwrap().body.id();
+
+    world
+        .body(id)
+        .unwrap()
+        .apply_force_to_center(Vec2::new(60.0, 0.0), true)
+        .unwrap();
+    world.step(DT, 4);
+
+    // v = F / m * dt
+    let velocity = world.body(id).unwrap().linear_velocity().unwrap();
+    assert_close(velocity.x, 1.0);
+    assert_close(velocity.y, 0.0);
+
+    // forces are cleared after each step
+    world.step(DT, 4);
+    let velocity = world.body(id).unwrap().linear_velocity().unwrap();
+    assert_close(velocity.x, 1.0);
+}
+
+#[test]
+fn off_center_impulse_spins_body() {
+    let mut world: World = common::zero_gravity_world();
+    let id = world.spawn(unit_box()).unwrap().body.id();
+
+    world
+        .body(id)
+        .unwrap()
+        .apply_linear_impulse(Vec2::new(1.0, 0.0), Vec2::new(0.0, 0.5), true)
+        .unwrap();
+    world.step(DT, 4);
+
+    let body = world.body(id).unwrap();
+    assert_close(body.linear_velocity().unwrap().x, 1.0);
+    // pushing right above the center turns the box clockwise
+    assert!(body.angular_velocity().unwrap() < 0.0);
+}
+
+#[test]
+fn torque_and_angular_impulse_spin_body() {
+    let mut world: World = common::zero_gravity_world();
+    let torqued = world.spawn(unit_box()).unwrap().body.id();
+    let kicked = world
+        .spawn(unit_box().at(Vec2::new(5.0, 0.0)))
+        .unwrap()
+        .body
+        .id();
+
+    world
+        .body(torqued)
+        .unwrap()
+        .apply_torque(10.0, true)
+        .unwrap();
+    world
+        .body(kicked)
+        .unwrap()
+        .apply_angular_impulse(-1.0, true)
+        .unwrap();
+    world.step(DT, 4);
+
+    assert!(world.body(torqued).unwrap().angular_velocity().unwrap() > 0.0);
+    assert!(world.body(kicked).unwrap().angular_velocity().unwrap() < 0.0);
+    assert_close(
+        world.body(torqued).unwrap().linear_velocity().unwrap().x,
+        0.0,
+    );
+}
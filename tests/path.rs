@@ -0,0 +1,224 @@
+use box2d3::{
+    path::{PathFollower, PathInterpolation, PathMode},
+    BodyBuilder, BodyId, CreateError, DefError, Vec2, World,
+};
+
+mod common;
+
+use common::{assert_close, assert_point};
+
+fn platform(world: &World, at: Vec2) -> BodyId {
+    world
+        .spawn(BodyBuilder::kinematic().at(at))
+        .unwrap()
+        .body
+        .id()
+}
+
+fn follower(world: &World, points: &[Vec2], mode: PathMode, speed: f32) -> PathFollower {
+    let body = platform(world, points[0]);
+    PathFollower::new(body, points, PathInterpolation::Linear, mode, speed).unwrap()
+}
+
+fn line() -> [Vec2; 2] {
+    [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)]
+}
+
+#[test]
+fn once_stops_at_the_end() {
+    let world = common::world();
+    let mut path = follower(&world, &line(), PathMode::Once, 4.0);
+    assert_close(path.length(), 10.0);
+
+    path.update(&world, 1.0).unwrap();
+    assert_close(path.distance(), 4.0);
+    assert!(!path.is_finished());
+
+    path.update(&world, 1.0).unwrap();
+    path.update(&world, 1.0).unwrap();
+    assert_close(path.distance(), 10.0);
+    assert!(path.is_finished());
+}
+
+#[test]
+fn loop_wraps_around_the_closing_segment() {
+    let world = common::world();
+    let square = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(10.0, 0.0),
+        Vec2::new(10.0, 10.0),
+        Vec2::new(0.0, 10.0),
+    ];
+    let mut path = follower(&world, &square, PathMode::Loop, 15.0);
+    // the closing segment back to the start counts too
+    assert_close(path.length(), 40.0);
+    assert_point(path.point_at(35.0), Vec2::new(0.0, 5.0));
+
+    for _ in 0..3 {
+        path.update(&world, 1.0).unwrap();
+    }
+    assert_close(path.distance(), 5.0);
+    assert!(!path.is_finished());
+}
+
+#[test]
+fn ping_pong_turns_around() {
+    let world = common::world();
+    let mut path = follower(&world, &line(), PathMode::PingPong, 4.0);
+
+    for _ in 0..3 {
+        path.update(&world, 1.0).unwrap();
+    }
+    // 12 meters travelled: to the end and 2 meters back
+    assert_close(path.distance(), 8.0);
+
+    for _ in 0..2 {
+        path.update(&world, 1.0).unwrap();
+    }
+    // a full round trip
+    assert_close(path.distance(), 0.0);
+    assert!(!path.is_finished());
+}
+
+#[test]
+fn update_moves_the_body_toward_the_next_point() {
+    let world = common::world();
+    let mut path = follower(&world, &line(), PathMode::Once, 2.0);
+
+    path.update(&world, 0.5).unwrap();
+    let velocity = world.body(path.body()).unwrap().linear_velocity().unwrap();
+    assert_close(velocity.x, 2.0);
+    assert_close(velocity.y, 0.0);
+}
+
+#[test]
+fn update_rejects_zero_time_step() {
+    let world = common::world();
+    let mut path = follower(&world, &line(), PathMode::Once, 2.0);
+
+    assert_eq!(
+        path.update(&world, 0.0),
+        Err(CreateError::InvalidDef(DefError::NotPositive("time_step")))
+    );
+    assert_close(path.distance(), 0.0);
+}
+
+#[test]
+fn point_at_skips_zero_length_spans() {
+    let world = common::world();
+    let points = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(0.0, 0.0),
+        Vec2::new(5.0, 0.0),
+        Vec2::new(5.0, 0.0),
+        Vec2::new(5.0, 5.0),
+    ];
+    let path = follower(&world, &points, PathMode::Once, 1.0);
+
+    assert_close(path.length(), 10.0);
+    assert_point(path.point_at(0.0), Vec2::new(0.0, 0.0));
+    assert_point(path.point_at(2.5), Vec2::new(2.5, 0.0));
+    assert_point(path.point_at(5.0), Vec2::new(5.0, 0.0));
+    assert_point(path.point_at(7.5), Vec2::new(5.0, 2.5));
+    // out of range distances are clamped
+    assert_point(path.point_at(-1.0), Vec2::new(0.0, 0.0));
+    assert_point(path.point_at(20.0), Vec2::new(5.0, 5.0));
+}
+
+#[test]
+fn single_point_path_stays_put() {
+    let world = common::world();
+    let point = Vec2::new(3.0, 4.0);
+    let mut path = follower(&world, &[point], PathMode::Loop, 1.0);
+
+    path.update(&world, 1.0).unwrap();
+    assert_close(path.length(), 0.0);
+    assert_close(path.distance(), 0.0);
+    assert_point(path.point_at(1.0), point);
+}
+
+#[test]
+fn spline_passes_through_the_waypoints() {
+    let world = common::world();
+    let points = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(10.0, 5.0),
+        Vec2::new(20.0, 0.0),
+    ];
+    let body = platform(&world, points[0]);
+    let path = PathFollower::new(
+        body,
+        &points,
+        PathInterpolation::Spline,
+        PathMode::Once,
+        1.0,
+    )
+    .unwrap();
+
+    assert_point(path.point_at(0.0), points[0]);
+    assert_point(path.point_at(path.length()), points[2]);
+
+    // the curve bends through the middle point, so it is longer than the straight lines
+    let polyline = (points[1] - points[0]).length() + (points[2] - points[1]).length();
+    assert!(path.length() >= polyline - 1e-3);
+    let closest = (0..=1000)
+        .map(|i| path.point_at(path.length() * i as f32 / 1000.0))
+        .map(|p| (p - points[1]).length())
+        .fold(f32::MAX, f32::min);
+    assert!(closest < 0.05, "{}", closest);
+}
+
+#[test]
+fn closed_spline_ends_where_it_starts() {
+    let world = common::world();
+    let points = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(10.0, 0.0),
+        Vec2::new(10.0, 10.0),
+        Vec2::new(0.0, 10.0),
+    ];
+    let body = platform(&world, points[0]);
+    let path = PathFollower::new(
+        body,
+        &points,
+        PathInterpolation::Spline,
+        PathMode::Loop,
+        1.0,
+    )
+    .unwrap();
+
+    assert_point(path.point_at(path.length()), points[0]);
+    // the curve swings out past each edge on its way round a corner
+    assert!(path.length() > 40.0);
+    assert!(path.length() < 45.0);
+}
+
+#[test]
+fn invalid_paths_are_rejected() {
+    let world = common::world();
+    let body = platform(&world, Vec2::ZERO);
+    let new = |points: &[Vec2], speed: f32| {
+        PathFollower::new(
+            body,
+            points,
+            PathInterpolation::Linear,
+            PathMode::Once,
+            speed,
+        )
+        .err()
+    };
+
+    assert_eq!(new(&[], 1.0), Some(DefError::EmptyPath));
+    assert_eq!(
+        new(&[Vec2::ZERO, Vec2::new(f32::NAN, 0.0)], 1.0),
+        Some(DefError::NotFinite("waypoints"))
+    );
+    assert_eq!(
+        new(&line(), f32::INFINITY),
+        Some(DefError::NotFinite("speed"))
+    );
+
+    let mut path = follower(&world, &line(), PathMode::Once, 1.0);
+    assert_eq!(path.set_speed(f32::NAN), Err(DefError::NotFinite("speed")));
+    assert_eq!(path.speed(), 1.0);
+}
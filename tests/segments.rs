@@ -0,0 +1,145 @@
+use box2d3::{
+    shapes::{Polygon, RayCastInput, Segment},
+    BodyBuilder, CreateError, DefError, Vec2, World,
+};
+
+mod common;
+
+use common::assert_close;
+
+fn floor() -> Segment {
+    Segment {
+        point1: Vec2::new(-5.0, 0.0),
+        point2: Vec2::new(5.0, 0.0),
+    }
+}
+
+#[test]
+fn ray_hits_segment_from_above() {
+    let output = floor().ray_cast(&RayCastInput::new(
+        Vec2::new(1.0, 2.0),
+        Vec2::new(0.0, -4.0),
+    ));
+
+    assert!(output.hit);
+    assert_close(output.fraction, 0.5);
+    assert_close(output.point.x, 1.0);
+    assert_close(output.point.y, 0.0);
+    assert_close(output.normal.y, 1.0);
+}
+
+#[test]
+fn ray_hits_segment_from_below() {
+    let output = floor().ray_cast(&RayCastInput::new(
+        Vec2::new(0.0, -1.0),
+        Vec2::new(0.0, 2.0),
+    ));
+
+    assert!(output.hit);
+    assert_close(output.fraction, 0.5);
+    assert_close(output.normal.y, -1.0);
+}
+
+#[test]
+fn ray_misses_segment() {
+    let segment = floor();
+
+    // passes beside the end point
+    let beside = segment.ray_cast(&RayCastInput::new(
+        Vec2::new(6.0, 2.0),
+        Vec2::new(0.0, -4.0),
+    ));
+    assert!(!beside.hit);
+
+    // stops short of the segment
+    let short = segment.ray_cast(&RayCastInput::new(
+        Vec2::new(0.0, 2.0),
+        Vec2::new(0.0, -1.0),
+    ));
+    assert!(!short.hit);
+
+    // parallel to the segment
+    let parallel = segment.ray_cast(&RayCastInput::new(
+        Vec2::new(-6.0, 0.0),
+        Vec2::new(12.0, 0.0),
+    ));
+    assert!(!parallel.hit);
+}
+
+#[test]
+fn degenerate_segment_is_rejected() {
+    let world: World = common::world();
+    let body = common::static_body(&world);
+
+    let point = Vec2::new(1.0, 1.0);
+    let result = body.create_shape_segment(
+        &Default::default(),
+        &Segment {
+            point1: point,
+            point2: point,
+        },
+    );
+    assert_eq!(
+        result.err(),
+        Some(CreateError::InvalidDef(DefError::DegenerateSegment))
+    );
+    assert_eq!(body.shape_count().unwrap(), 0);
+}
+
+#[test]
+fn circle_rests_on_segment() {
+    let mut world: World = common::world();
+    let ground = world
+        .spawn(BodyBuilder::static_body().with_segment(floor()))
+        .unwrap();
+    let ground_shape = ground.shapes[0].id();
+
+    let ball = world
+        .spawn(
+            BodyBuilder::dynamic()
+                .at(Vec2::new(0.0, 2.0))
+                .with_circle(common::ball(0.5)),
+        )
+        .unwrap();
+    let ball_id = ball.body.id();
+    let ball_shape = ball.shapes[0].id();
+
+    common::run(&mut world, 120);
+
+    let ball = world.body(ball_id).unwrap();
+    assert!((ball.position().unwrap().y - 0.5).abs() < 0.05);
+    assert!(ball.linear_velocity().unwrap().length() < 0.01);
+
+    let contacts = ball.contact_data().unwrap();
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts[0].other_shape(ball_shape), ground_shape);
+}
+
+#[test]
+fn box_slides_off_sloped_segment() {
+    let mut world: World = common::world();
+    world
+        .spawn(BodyBuilder::static_body().with_segment(Segment {
+            point1: Vec2::new(-5.0, 5.0),
+            point2: Vec2::new(5.0, -5.0),
+        }))
+        .unwrap();
+
+    let id = world
+        .spawn(
+            BodyBuilder::dynamic()
+                .at(Vec2::new(-2.0, 4.0))
+                .friction(0.0)
+                .with_polygon(Polygon::new_box(0.5, 0.5)),
+        )
+        .unwrap()
+        .body
+        .id();
+
+    common::run(&mut world, 60);
+
+    // the segment stops the box from falling straight down and pushes it to the right
+    let position = world.body(id).unwrap().position().unwrap();
+    assert!(position.x > -2.0);
+    assert!(position.y > -position.x);
+}
@@ -0,0 +1,182 @@
+use std::f32::consts::PI;
+
+use box2d3::{
+    shapes::{Capsule, Circle, Geometry, Hull, Polygon, ShapeType},
+    BodyBuilder, CreateError, DefError, HullError, Vec2, World,
+};
+
+mod common;
+
+#[test]
+fn material_setters_reject_bad_values() {
+    let world: World = common::world();
+    let spawned = world
+        .spawn(BodyBuilder::dynamic().with_polygon(Polygon::new_box(0.5, 0.5)))
+        .unwrap();
+    let shape = spawned.shapes[0];
+
+    assert_eq!(
+        shape.set_friction(-0.1),
+        Err(CreateError::InvalidDef(DefError::Negative("friction")))
+    );
+    assert_eq!(
+        shape.set_restitution(f32::NAN),
+        Err(CreateError::InvalidDef(DefError::NotFinite("restitution")))
+    );
+    assert_eq!(
+        shape.set_density(-1.0, true),
+        Err(CreateError::InvalidDef(DefError::Negative("density")))
+    );
+
+    shape.set_friction(0.2).unwrap();
+    shape.set_restitution(0.5).unwrap();
+    shape.set_density(2.0, true).unwrap();
+    assert_eq!(shape.friction().unwrap(), 0.2);
+    assert_eq!(shape.restitution().unwrap(), 0.5);
+    assert_eq!(shape.density().unwrap(), 2.0);
+    // a one metre box at density two
+    assert!((spawned.body.mass().unwrap() - 2.0).abs() < 1e-3);
+}
+
+#[test]
+fn material_setters_reject_stale_shapes() {
+    let world: World = common::world();
+    let spawned = world
+        .spawn(BodyBuilder::dynamic().with_polygon(Polygon::new_box(0.5, 0.5)))
+        .unwrap();
+    let shape = spawned.shapes[0];
+    spawned.body.destroy().unwrap();
+
+    assert_eq!(shape.set_friction(0.2), Err(CreateError::StaleHandle));
+    assert_eq!(shape.set_density(1.0, false), Err(CreateError::StaleHandle));
+}
+
+#[test]
+fn changing_geometry_updates_automatic_mass() {
+    let world: World = common::world();
+    let spawned = world
+        .spawn(BodyBuilder::dynamic().with_polygon(Polygon::new_box(0.5, 0.5)))
+        .unwrap();
+    let (body, shape) = (spawned.body, spawned.shapes[0]);
+    assert!((body.mass().unwrap() - 1.0).abs() < 1e-3);
+
+    shape.set_circle(&common::ball(1.0)).unwrap();
+    assert!((body.mass().unwrap() - PI).abs() < 1e-3);
+
+    shape.set_polygon(&Polygon::new_box(1.0, 1.0)).unwrap();
+    assert!((body.mass().unwrap() - 4.0).abs() < 1e-3);
+}
+
+#[test]
+fn changing_geometry_keeps_manual_mass() {
+    let world: World = common::world();
+    let spawned = world
+        .spawn(
+            BodyBuilder::dynamic()
+                .automatic_mass(false)
+                .with_polygon(Polygon::new_box(0.5, 0.5)),
+        )
+        .unwrap();
+    let (body, shape) = (spawned.body, spawned.shapes[0]);
+    body.apply_mass_from_shapes().unwrap();
+    let mass_data = body.mass_data().unwrap();
+
+    shape.set_circle(&common::ball(1.0)).unwrap();
+    shape.set_polygon(&Polygon::new_box(1.0, 1.0)).unwrap();
+    assert_eq!(body.mass_data().unwrap(), mass_data);
+}
+
+#[test]
+fn collapsed_capsule_becomes_a_circle() {
+    let world: World = common::world();
+    let spawned = world
+        .spawn(BodyBuilder::dynamic().with_polygon(Polygon::new_box(0.5, 0.5)))
+        .unwrap();
+    let shape = spawned.shapes[0];
+
+    shape
+        .set_capsule(&Capsule {
+            center1: Vec2::new(1.0, 0.0),
+            center2: Vec2::new(1.0, 0.0),
+            radius: 0.5,
+        })
+        .unwrap();
+    assert_eq!(shape.shape_type().unwrap(), ShapeType::Circle);
+    assert_eq!(
+        shape.geometry().unwrap(),
+        Some(Geometry::Circle(Circle {
+            center: Vec2::new(1.0, 0.0),
+            radius: 0.5,
+        }))
+    );
+}
+
+#[test]
+fn hull_drops_interior_points() {
+    let hull = Hull::compute(&[
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(1.0, 0.5),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(0.0, 2.0),
+    ])
+    .unwrap();
+    assert_eq!(hull.points().len(), 4);
+    assert!(!hull.points().contains(&Vec2::new(1.0, 0.5)));
+}
+
+#[test]
+fn hull_rejects_bad_points() {
+    let square = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(0.0, 1.0),
+    ];
+
+    assert_eq!(
+        Hull::compute(&square[..2]).err(),
+        Some(HullError::PointCount(2))
+    );
+    assert_eq!(
+        Hull::compute(&[Vec2::ZERO; 9]).err(),
+        Some(HullError::PointCount(9))
+    );
+
+    let mut not_finite = square;
+    not_finite[2].y = f32::NAN;
+    assert_eq!(Hull::compute(&not_finite).err(), Some(HullError::NotFinite));
+
+    let collinear = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(2.0, 0.0),
+    ];
+    assert_eq!(Hull::compute(&collinear).err(), Some(HullError::Degenerate));
+}
+
+#[test]
+fn box_builders_reject_bad_extents() {
+    assert_eq!(
+        Polygon::new_square(0.0).err(),
+        Some(DefError::NotPositive("h"))
+    );
+    assert_eq!(
+        Polygon::new_rounded_box(1.0, -1.0, 0.1).err(),
+        Some(DefError::NotPositive("hy"))
+    );
+    assert_eq!(
+        Polygon::new_rounded_box(1.0, 1.0, -0.1).err(),
+        Some(DefError::Negative("radius"))
+    );
+    assert_eq!(
+        Polygon::new_rounded_box_ex(1.0, 1.0, Vec2::ZERO, f32::NAN, 0.1).err(),
+        Some(DefError::NotFinite("angle"))
+    );
+
+    let square = Polygon::new_square(0.5).unwrap();
+    assert_eq!(square.vertices().len(), 4);
+    let rounded = Polygon::new_rounded_box_ex(1.0, 0.5, Vec2::new(1.0, 0.0), 0.0, 0.1).unwrap();
+    assert_eq!(rounded.radius(), 0.1);
+    assert!((rounded.centroid() - Vec2::new(1.0, 0.0)).length() < 1e-5);
+}
@@ -0,0 +1,119 @@
+use std::thread;
+
+use box2d3::{
+    body::BodyKind, shapes::Polygon, world::MAX_WORKERS, BodyDef, DefError, ShapeDef, World,
+    WorldDef,
+};
+
+mod common;
+
+fn assert_send<T: Send>() {}
+
+fn build_world() -> World<u32> {
+    let world = common::world();
+
+    let ground = world.create_body(&BodyDef::default()).unwrap();
+    ground
+        .create_shape_polygon(&ShapeDef::default(), &Polygon::new_box(50.0, 1.0))
+        .unwrap();
+
+    for i in 0..10 {
+        let mut def = BodyDef::default();
+        def.kind = BodyKind::Dynamic;
+        def.position.y = 2.0 + i as f32 * 2.0;
+        let body = world.create_body(&def).unwrap();
+        body.create_shape_polygon(&ShapeDef::default(), &Polygon::new_box(0.5, 0.5))
+            .unwrap();
+        body.set_user_data(i).unwrap();
+    }
+
+    world
+}
+
+#[test]
+fn world_is_send() {
+    assert_send::<World>();
+    assert_send::<World<u32, String>>();
+}
+
+#[test]
+fn world_moves_between_threads() {
+    let world = thread::spawn(build_world).join().unwrap();
+
+    let mut world = thread::spawn(move || {
+        let mut world = world;
+        common::run(&mut world, 60);
+        world
+    })
+    .join()
+    .unwrap();
+
+    world.step(1.0 / 60.0, 4);
+    assert!(world.is_valid());
+}
+
+#[test]
+fn worlds_step_concurrently() {
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            thread::spawn(|| {
+                let mut world = build_world();
+                common::run(&mut world, 120);
+                world.is_valid()
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        assert!(thread.join().unwrap());
+    }
+}
+
+#[test]
+fn world_steps_with_worker_threads() {
+    let mut def = WorldDef::default();
+    def.worker_count = 4;
+    let mut world: World = World::new(&def).unwrap();
+
+    for i in 0..100 {
+        let mut def = BodyDef::default();
+        def.kind = BodyKind::Dynamic;
+        def.position.x = (i % 10) as f32 * 1.1;
+        def.position.y = (i / 10) as f32 * 1.1;
+        let body = world.create_body(&def).unwrap();
+        body.create_shape_polygon(&ShapeDef::default(), &Polygon::new_box(0.5, 0.5))
+            .unwrap();
+    }
+
+    common::run(&mut world, 120);
+    assert!(world.is_valid());
+}
+
+#[test]
+fn world_steps_with_max_workers() {
+    let mut def = WorldDef::default();
+    def.worker_count = MAX_WORKERS;
+    let mut world: World = World::new(&def).unwrap();
+
+    for i in 0..MAX_WORKERS {
+        let mut def = BodyDef::default();
+        def.kind = BodyKind::Dynamic;
+        def.position.x = i as f32 * 1.1;
+        let body = world.create_body(&def).unwrap();
+        body.create_shape_polygon(&ShapeDef::default(), &Polygon::new_box(0.5, 0.5))
+            .unwrap();
+    }
+
+    common::run(&mut world, 10);
+    assert!(world.is_valid());
+}
+
+#[test]
+fn too_many_workers_is_rejected() {
+    let mut def = WorldDef::default();
+    def.worker_count = MAX_WORKERS + 1;
+    assert_eq!(
+        World::<(), ()>::new(&def).err(),
+        Some(DefError::TooManyWorkers)
+    );
+}
@@ -0,0 +1,139 @@
+use std::{cell::Cell, rc::Rc};
+
+use box2d3::{BodyBuilder, World};
+
+mod common;
+
+/// User data that counts how often it has been dropped.
+struct Tracked {
+    name: &'static str,
+    drops: Rc<Cell<u32>>,
+}
+
+impl Tracked {
+    fn new(name: &'static str, drops: &Rc<Cell<u32>>) -> Self {
+        Self {
+            name,
+            drops: drops.clone(),
+        }
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+type TrackedWorld = World<Tracked, Tracked>;
+
+#[test]
+fn replacing_user_data_drops_the_old_value() {
+    let drops = Rc::new(Cell::new(0));
+    let world: TrackedWorld = common::world();
+    let body = world
+        .spawn(BodyBuilder::dynamic().user_data(Tracked::new("first", &drops)))
+        .unwrap()
+        .body;
+
+    body.set_user_data(Tracked::new("second", &drops)).unwrap();
+    assert_eq!(drops.get(), 1);
+    assert_eq!(body.user_data().unwrap().unwrap().name, "second");
+
+    body.clear_user_data().unwrap();
+    assert_eq!(drops.get(), 2);
+    assert!(body.user_data().unwrap().is_none());
+}
+
+#[test]
+fn destroying_drops_user_data() {
+    let drops = Rc::new(Cell::new(0));
+    let world: TrackedWorld = common::world();
+    let spawned = world
+        .spawn(
+            BodyBuilder::dynamic()
+                .user_data(Tracked::new("body", &drops))
+                .with_circle(common::ball(0.5))
+                .with_circle(common::ball(0.5)),
+        )
+        .unwrap();
+    spawned.shapes[0]
+        .set_user_data(Tracked::new("first shape", &drops))
+        .unwrap();
+    spawned.shapes[1]
+        .set_user_data(Tracked::new("second shape", &drops))
+        .unwrap();
+
+    spawned.shapes[0].destroy().unwrap();
+    assert_eq!(drops.get(), 1);
+
+    spawned.body.destroy().unwrap();
+    assert_eq!(drops.get(), 3);
+    assert_eq!(world.body_count(), 0);
+    assert_eq!(world.shape_count(), 0);
+}
+
+#[test]
+fn dropping_the_world_drops_user_data() {
+    let drops = Rc::new(Cell::new(0));
+    {
+        let world: TrackedWorld = common::world();
+        for _ in 0..3 {
+            world
+                .spawn(BodyBuilder::dynamic().user_data(Tracked::new("body", &drops)))
+                .unwrap();
+        }
+    }
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn user_data_survives_stepping() {
+    let drops = Rc::new(Cell::new(0));
+    let mut world: TrackedWorld = common::world();
+    let spawned = world
+        .spawn(
+            BodyBuilder::dynamic()
+                .user_data(Tracked::new("player", &drops))
+                .with_circle(common::ball(0.5)),
+        )
+        .unwrap();
+    let body_id = spawned.body.id();
+    let shape_id = spawned.shapes[0].id();
+    spawned.shapes[0]
+        .set_user_data(Tracked::new("feet", &drops))
+        .unwrap();
+    world
+        .spawn(BodyBuilder::dynamic().user_data(Tracked::new("enemy", &drops)))
+        .unwrap();
+
+    common::run(&mut world, 10);
+
+    assert_eq!(drops.get(), 0);
+    let body = world.body(body_id).unwrap();
+    assert_eq!(body.user_data().unwrap().unwrap().name, "player");
+    let shape = world.shape(shape_id).unwrap();
+    assert_eq!(shape.user_data().unwrap().unwrap().name, "feet");
+
+    let found = world.find_body(|data| data.name == "enemy").unwrap();
+    assert_ne!(found.id(), body_id);
+    assert_eq!(
+        world.find_shape(|data| data.name == "feet").unwrap().id(),
+        shape_id
+    );
+    assert!(world.find_body(|data| data.name == "missing").is_none());
+}
+
+#[test]
+#[should_panic]
+fn replacing_borrowed_user_data_panics() {
+    let drops = Rc::new(Cell::new(0));
+    let world: TrackedWorld = common::world();
+    let body = world
+        .spawn(BodyBuilder::dynamic().user_data(Tracked::new("first", &drops)))
+        .unwrap()
+        .body;
+
+    let _data = body.user_data().unwrap();
+    let _ = body.set_user_data(Tracked::new("second", &drops));
+}